@@ -1,13 +1,24 @@
 use anyhow::Result;
 use lib::config::CliConfig;
+use lib::timezone::TimeFormat;
+use lib::weather::units::UnitSystem;
 
+use super::location::local_location_label;
+use crate::client::ApiClient;
 use crate::ConfigCommands;
 
 pub async fn handle(command: ConfigCommands, config: &mut CliConfig) -> Result<()> {
     match command {
         ConfigCommands::Show => {
+            let default_location = match config.default_location_id {
+                Some(id) => local_location_label(&ApiClient::new(config), id).await,
+                None => "(server default)".to_string(),
+            };
             println!("Server URL: {}", config.server_url);
             println!("API Token: {}", if config.api_token.is_empty() { "(not set)" } else { "********" });
+            println!("Default Location: {}", default_location);
+            println!("Time Format: {}", config.time_format.as_deref().unwrap_or("24h"));
+            println!("Units: {}", config.units.unwrap_or_default());
         }
         ConfigCommands::SetServer { url } => {
             config.server_url = url.clone();
@@ -19,6 +30,19 @@ pub async fn handle(command: ConfigCommands, config: &mut CliConfig) -> Result<(
             config.save()?;
             println!("API token updated");
         }
+        ConfigCommands::SetTimeFormat { format } => {
+            if let Some(format) = &format {
+                format.parse::<TimeFormat>()?;
+            }
+            config.time_format = format;
+            config.save()?;
+            println!("Time format set to: {}", config.time_format.as_deref().unwrap_or("24h"));
+        }
+        ConfigCommands::SetUnits { units } => {
+            config.units = units.map(|units| units.parse::<UnitSystem>()).transpose()?;
+            config.save()?;
+            println!("Units set to: {}", config.units.unwrap_or_default());
+        }
     }
 
     Ok(())
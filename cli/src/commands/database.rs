@@ -1,17 +1,93 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use lib::config::CliConfig;
 use serde::{Deserialize, Serialize};
+use std::fs;
 use std::io::{self, Write};
+use std::path::Path;
 use uuid::Uuid;
 
-use crate::{client::ApiClient, DatabaseCommands};
+use crate::{client::ApiClient, DatabaseCommands, DatabaseServerCommands, EnvFormat};
 
 #[derive(Debug, Serialize)]
 struct CreateDatabaseRequest {
     db_name: String,
     owner: Option<String>,
     notes: Option<String>,
+    protected: bool,
+    server: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateDatabaseServerRequest {
+    name: String,
+    admin_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DatabaseServerResponse {
+    name: String,
+    host: String,
+    port: u16,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DatabaseConnectionInfo {
+    db_name: String,
+    host: String,
+    port: u16,
+    username: String,
+    password: Option<String>,
+    database_url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SetProtectionRequest {
+    protected: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ExecSqlRequest {
+    sql: String,
+}
+
+#[derive(Debug, Serialize)]
+struct MigrationFile {
+    name: String,
+    sql: String,
+}
+
+#[derive(Debug, Serialize)]
+struct MigrateRequest {
+    migrations: Vec<MigrationFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatementResult {
+    statement: String,
+    rows_affected: Option<u64>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExecSqlResponse {
+    db_name: String,
+    committed: bool,
+    statements: Vec<StatementResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MigrationResult {
+    name: String,
+    status: String,
+    statements: Vec<StatementResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MigrateResponse {
+    db_name: String,
+    migrations: Vec<MigrationResult>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -21,33 +97,83 @@ struct ManagedDatabase {
     created_at: DateTime<Utc>,
     owner: Option<String>,
     notes: Option<String>,
+    deleted_at: Option<DateTime<Utc>>,
+    protected: bool,
+    server: Option<String>,
 }
 
 pub async fn handle(command: DatabaseCommands, config: &CliConfig) -> Result<()> {
     let client = ApiClient::new(config);
 
     match command {
-        DatabaseCommands::Create { name } => {
-            create_database(&client, name).await?;
+        DatabaseCommands::Create {
+            name,
+            protected,
+            on,
+        } => {
+            create_database(&client, name, protected, on).await?;
+        }
+        DatabaseCommands::List { deleted } => {
+            list_databases(&client, deleted).await?;
+        }
+        DatabaseCommands::Drop { name, hard } => {
+            drop_database(&client, name, hard).await?;
+        }
+        DatabaseCommands::Undelete { name } => {
+            undelete_database(&client, name).await?;
+        }
+        DatabaseCommands::Protect { name } => {
+            set_protection(&client, name, true).await?;
         }
-        DatabaseCommands::List => {
-            list_databases(&client).await?;
+        DatabaseCommands::Unprotect { name } => {
+            set_protection(&client, name, false).await?;
         }
-        DatabaseCommands::Drop { name } => {
-            drop_database(&client, name).await?;
+        DatabaseCommands::Env {
+            name,
+            format,
+            write,
+        } => {
+            database_env(&client, name, format, write).await?;
+        }
+        DatabaseCommands::Server { command } => match command {
+            DatabaseServerCommands::Add { name, admin_url } => {
+                add_server(&client, name, admin_url).await?;
+            }
+            DatabaseServerCommands::List => {
+                list_servers(&client).await?;
+            }
+            DatabaseServerCommands::Rm { name } => {
+                remove_server(&client, name).await?;
+            }
+        },
+        DatabaseCommands::Exec { name, file } => {
+            exec_sql(&client, name, file).await?;
+        }
+        DatabaseCommands::Migrate { name, dir } => {
+            migrate_database(&client, name, dir).await?;
         }
     }
 
     Ok(())
 }
 
-async fn create_database(client: &ApiClient, name: String) -> Result<()> {
-    println!("Creating database '{}'...", name);
+async fn create_database(
+    client: &ApiClient,
+    name: String,
+    protected: bool,
+    server: Option<String>,
+) -> Result<()> {
+    match &server {
+        Some(server) => println!("Creating database '{}' on '{}'...", name, server),
+        None => println!("Creating database '{}'...", name),
+    }
 
     let req = CreateDatabaseRequest {
         db_name: name.clone(),
         owner: None,
         notes: None,
+        protected,
+        server,
     };
 
     let response = client.post("/api/v1/databases", &req).await?;
@@ -64,12 +190,23 @@ async fn create_database(client: &ApiClient, name: String) -> Result<()> {
     println!("  Name: {}", database.db_name);
     println!("  ID: {}", database.id);
     println!("  Created: {}", database.created_at.format("%Y-%m-%d %H:%M:%S UTC"));
+    if let Some(server) = &database.server {
+        println!("  Server: {}", server);
+    }
+    if database.protected {
+        println!("  Protected: yes");
+    }
 
     Ok(())
 }
 
-async fn list_databases(client: &ApiClient) -> Result<()> {
-    let response = client.get("/api/v1/databases").await?;
+async fn list_databases(client: &ApiClient, deleted: bool) -> Result<()> {
+    let url = if deleted {
+        "/api/v1/databases?deleted=true"
+    } else {
+        "/api/v1/databases"
+    };
+    let response = client.get(url).await?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -80,15 +217,32 @@ async fn list_databases(client: &ApiClient) -> Result<()> {
     let databases: Vec<ManagedDatabase> = response.json().await?;
 
     if databases.is_empty() {
-        println!("No managed databases found.");
+        if deleted {
+            println!("Trash is empty.");
+        } else {
+            println!("No managed databases found.");
+        }
         return Ok(());
     }
 
-    println!("Managed Databases:");
+    if deleted {
+        println!("Trashed Databases:");
+    } else {
+        println!("Managed Databases:");
+    }
     println!();
     for db in databases {
         println!("  {} - {}", db.db_name, db.id);
         println!("    Created: {}", db.created_at.format("%Y-%m-%d %H:%M:%S UTC"));
+        if let Some(server) = &db.server {
+            println!("    Server: {}", server);
+        }
+        if db.protected {
+            println!("    Protected: yes");
+        }
+        if let Some(deleted_at) = &db.deleted_at {
+            println!("    Deleted: {}", deleted_at.format("%Y-%m-%d %H:%M:%S UTC"));
+        }
         if let Some(owner) = &db.owner {
             println!("    Owner: {}", owner);
         }
@@ -101,23 +255,31 @@ async fn list_databases(client: &ApiClient) -> Result<()> {
     Ok(())
 }
 
-async fn drop_database(client: &ApiClient, name: String) -> Result<()> {
-    // Confirmation prompt
-    print!("WARNING: This will permanently delete database '{}'. Continue? (yes/no): ", name);
-    io::stdout().flush()?;
+async fn drop_database(client: &ApiClient, name: String, hard: bool) -> Result<()> {
+    if hard {
+        // Confirmation prompt
+        print!("WARNING: This will permanently delete database '{}'. Continue? (yes/no): ", name);
+        io::stdout().flush()?;
 
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-    let input = input.trim().to_lowercase();
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let input = input.trim().to_lowercase();
 
-    if input != "yes" && input != "y" {
-        println!("Operation cancelled.");
-        return Ok(());
-    }
+        if input != "yes" && input != "y" {
+            println!("Operation cancelled.");
+            return Ok(());
+        }
 
-    println!("Dropping database '{}'...", name);
+        println!("Dropping database '{}'...", name);
+    } else {
+        println!("Moving database '{}' to the trash...", name);
+    }
 
-    let url = format!("/api/v1/databases/{}", name);
+    let url = if hard {
+        format!("/api/v1/databases/{}?hard=true", name)
+    } else {
+        format!("/api/v1/databases/{}", name)
+    };
     let response = client.delete(&url).await?;
 
     if !response.status().is_success() {
@@ -128,7 +290,373 @@ async fn drop_database(client: &ApiClient, name: String) -> Result<()> {
 
     let database: ManagedDatabase = response.json().await?;
 
-    println!("Database '{}' dropped successfully!", database.db_name);
+    if hard {
+        println!("Database '{}' dropped successfully!", database.db_name);
+    } else {
+        println!("Database '{}' moved to the trash.", database.db_name);
+        println!("\nRestore it with: cs db undelete {}", database.db_name);
+    }
 
     Ok(())
 }
+
+async fn undelete_database(client: &ApiClient, name: String) -> Result<()> {
+    println!("Restoring database '{}' from the trash...", name);
+
+    let url = format!("/api/v1/databases/{}/undelete", name);
+    let response = client.post(&url, &serde_json::json!({})).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to restore database: {} - {}", status, body);
+    }
+
+    let database: ManagedDatabase = response.json().await?;
+
+    println!("Database '{}' restored successfully!", database.db_name);
+
+    Ok(())
+}
+
+async fn set_protection(client: &ApiClient, name: String, protected: bool) -> Result<()> {
+    let url = format!("/api/v1/databases/{}/protection", name);
+    let response = client.put(&url, &SetProtectionRequest { protected }).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to update protection: {} - {}", status, body);
+    }
+
+    let database: ManagedDatabase = response.json().await?;
+
+    if database.protected {
+        println!("Database '{}' is now protected from being dropped.", database.db_name);
+    } else {
+        println!("Database '{}' is no longer protected.", database.db_name);
+    }
+
+    Ok(())
+}
+
+async fn database_env(
+    client: &ApiClient,
+    name: String,
+    format: EnvFormat,
+    write: Option<String>,
+) -> Result<()> {
+    let url = format!("/api/v1/databases/{}/connection", name);
+    let response = client.get(&url).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to get connection settings: {} - {}", status, body);
+    }
+
+    let info: DatabaseConnectionInfo = response.json().await?;
+    let vars = env_vars(&info);
+
+    let Some(dir) = write else {
+        print!("{}", render_env(&vars, format)?);
+        return Ok(());
+    };
+
+    let path = Path::new(&dir).join(".env");
+    let existing = if path.exists() {
+        fs::read_to_string(&path).context(format!("Failed to read '{}'", path.display()))?
+    } else {
+        String::new()
+    };
+
+    fs::write(&path, merge_dotenv(&existing, &vars))
+        .context(format!("Failed to write '{}'", path.display()))?;
+
+    println!("Wrote connection settings for '{}' to {}", info.db_name, path.display());
+
+    Ok(())
+}
+
+fn env_vars(info: &DatabaseConnectionInfo) -> Vec<(&'static str, String)> {
+    let mut vars = vec![
+        ("DATABASE_URL", info.database_url.clone()),
+        ("PGHOST", info.host.clone()),
+        ("PGPORT", info.port.to_string()),
+        ("PGDATABASE", info.db_name.clone()),
+        ("PGUSER", info.username.clone()),
+    ];
+    if let Some(password) = &info.password {
+        vars.push(("PGPASSWORD", password.clone()));
+    }
+    vars
+}
+
+fn render_env(vars: &[(&str, String)], format: EnvFormat) -> Result<String> {
+    let output = match format {
+        EnvFormat::Dotenv => vars
+            .iter()
+            .map(|(key, value)| format!("{}={}\n", key, dotenv_value(value)))
+            .collect(),
+        EnvFormat::Shell => vars
+            .iter()
+            .map(|(key, value)| format!("export {}='{}'\n", key, value.replace('\'', "'\\''")))
+            .collect(),
+        EnvFormat::Json => {
+            let map: serde_json::Map<String, serde_json::Value> = vars
+                .iter()
+                .map(|(key, value)| (key.to_string(), serde_json::Value::String(value.clone())))
+                .collect();
+            format!("{}\n", serde_json::to_string_pretty(&map)?)
+        }
+    };
+
+    Ok(output)
+}
+
+/// Quote a dotenv value if it contains characters dotenv parsers would otherwise mangle
+fn dotenv_value(value: &str) -> String {
+    if value
+        .chars()
+        .any(|c| c.is_whitespace() || matches!(c, '#' | '"' | '\'' | '$' | '\\'))
+    {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Replace our keys in an existing .env file, keeping every other line, and append missing keys
+fn merge_dotenv(existing: &str, vars: &[(&str, String)]) -> String {
+    let mut written = Vec::new();
+    let mut lines: Vec<String> = existing
+        .lines()
+        .map(|line| {
+            let key = line
+                .trim_start()
+                .trim_start_matches("export ")
+                .split('=')
+                .next()
+                .unwrap_or("")
+                .trim();
+            match vars.iter().find(|(k, _)| *k == key) {
+                Some((k, value)) => {
+                    written.push(*k);
+                    format!("{}={}", k, dotenv_value(value))
+                }
+                None => line.to_string(),
+            }
+        })
+        .collect();
+
+    for (key, value) in vars {
+        if !written.contains(key) {
+            lines.push(format!("{}={}", key, dotenv_value(value)));
+        }
+    }
+
+    let mut output = lines.join("\n");
+    output.push('\n');
+    output
+}
+
+async fn add_server(client: &ApiClient, name: String, admin_url: String) -> Result<()> {
+    println!("Registering database server '{}'...", name);
+
+    let req = CreateDatabaseServerRequest { name, admin_url };
+    let response = client.post("/api/v1/database-servers", &req).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to register database server: {} - {}", status, body);
+    }
+
+    let server: DatabaseServerResponse = response.json().await?;
+
+    println!("Database server registered!");
+    println!("  Name: {}", server.name);
+    println!("  Host: {}:{}", server.host, server.port);
+    println!("\nCreate databases on it with: cs db create <name> --on {}", server.name);
+
+    Ok(())
+}
+
+async fn list_servers(client: &ApiClient) -> Result<()> {
+    let response = client.get("/api/v1/database-servers").await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to list database servers: {} - {}", status, body);
+    }
+
+    let servers: Vec<DatabaseServerResponse> = response.json().await?;
+
+    if servers.is_empty() {
+        println!("No database servers registered. Databases are created on the control server.");
+        return Ok(());
+    }
+
+    println!("Database Servers:");
+    println!();
+    for server in servers {
+        println!("  {} - {}:{}", server.name, server.host, server.port);
+        println!("    Registered: {}", server.created_at.format("%Y-%m-%d %H:%M:%S UTC"));
+    }
+
+    Ok(())
+}
+
+async fn remove_server(client: &ApiClient, name: String) -> Result<()> {
+    let url = format!("/api/v1/database-servers/{}", name);
+    let response = client.delete(&url).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to remove database server: {} - {}", status, body);
+    }
+
+    let server: DatabaseServerResponse = response.json().await?;
+
+    println!("Database server '{}' removed.", server.name);
+
+    Ok(())
+}
+
+async fn exec_sql(client: &ApiClient, name: String, file: String) -> Result<()> {
+    let sql = fs::read_to_string(&file).context(format!("Failed to read '{}'", file))?;
+
+    println!("Executing '{}' against database '{}'...", file, name);
+
+    let url = format!("/api/v1/databases/{}/exec", name);
+    let response = client.post(&url, &ExecSqlRequest { sql }).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to execute SQL: {} - {}", status, body);
+    }
+
+    let result: ExecSqlResponse = response.json().await?;
+
+    print_statement_results(&result.statements);
+    println!();
+
+    if result.committed {
+        println!(
+            "Executed {} statement(s) against '{}'. Transaction committed.",
+            result.statements.len(),
+            result.db_name
+        );
+    } else {
+        anyhow::bail!("Execution failed. Transaction rolled back, no changes were made.");
+    }
+
+    Ok(())
+}
+
+async fn migrate_database(client: &ApiClient, name: String, dir: String) -> Result<()> {
+    let mut migrations = Vec::new();
+
+    for entry in fs::read_dir(&dir).context(format!("Failed to read directory '{}'", dir))? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("sql") {
+            continue;
+        }
+
+        let file_name = migration_name(&path)?;
+        let sql = fs::read_to_string(&path)
+            .context(format!("Failed to read '{}'", path.display()))?;
+
+        migrations.push(MigrationFile {
+            name: file_name,
+            sql,
+        });
+    }
+
+    if migrations.is_empty() {
+        println!("No .sql files found in '{}'.", dir);
+        return Ok(());
+    }
+
+    migrations.sort_by(|a, b| a.name.cmp(&b.name));
+
+    println!(
+        "Applying {} migration(s) from '{}' to database '{}'...",
+        migrations.len(),
+        dir,
+        name
+    );
+
+    let url = format!("/api/v1/databases/{}/migrate", name);
+    let response = client.post(&url, &MigrateRequest { migrations }).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to migrate database: {} - {}", status, body);
+    }
+
+    let result: MigrateResponse = response.json().await?;
+
+    println!();
+    let mut failed = false;
+    for migration in &result.migrations {
+        println!("  {} [{}]", migration.name, migration.status);
+        if migration.status == "failed" {
+            failed = true;
+            print_statement_results(&migration.statements);
+        }
+    }
+    println!();
+
+    if failed {
+        anyhow::bail!("Migration of '{}' failed. The failing migration was rolled back.", result.db_name);
+    }
+
+    let applied = result
+        .migrations
+        .iter()
+        .filter(|m| m.status == "applied")
+        .count();
+    println!("Database '{}' is up to date ({} applied).", result.db_name, applied);
+
+    Ok(())
+}
+
+fn migration_name(path: &Path) -> Result<String> {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.to_string())
+        .context(format!("Invalid migration file name: {}", path.display()))
+}
+
+fn print_statement_results(statements: &[StatementResult]) {
+    for (index, result) in statements.iter().enumerate() {
+        let preview = statement_preview(&result.statement);
+        match (&result.error, result.rows_affected) {
+            (Some(error), _) => {
+                println!("  [{}] FAILED  {}", index + 1, preview);
+                println!("        {}", error);
+            }
+            (None, Some(rows)) => println!("  [{}] OK ({} rows)  {}", index + 1, rows, preview),
+            (None, None) => println!("  [{}] OK  {}", index + 1, preview),
+        }
+    }
+}
+
+fn statement_preview(statement: &str) -> String {
+    let first_line = statement
+        .lines()
+        .map(|l| l.trim())
+        .find(|l| !l.is_empty() && !l.starts_with("--"))
+        .unwrap_or("");
+
+    if first_line.chars().count() > 60 {
+        format!("{}...", first_line.chars().take(57).collect::<String>())
+    } else {
+        first_line.to_string()
+    }
+}
@@ -0,0 +1,113 @@
+use anyhow::Result;
+use lib::config::CliConfig;
+use serde::Deserialize;
+
+use crate::client::ApiClient;
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResult {
+    city_name: String,
+    country: String,
+    state: Option<String>,
+    latitude: f64,
+    longitude: f64,
+    timezone: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PurgeGeocodingCacheResponse {
+    purged: u64,
+}
+
+pub async fn handle(
+    query: Option<String>,
+    limit: Option<usize>,
+    purge_cache: bool,
+    expired: bool,
+    config: &CliConfig,
+) -> Result<()> {
+    let client = ApiClient::new(config);
+
+    match query {
+        Some(query) if !purge_cache => lookup(&client, &query, limit).await,
+        _ => purge(&client, expired).await,
+    }
+}
+
+async fn lookup(client: &ApiClient, query: &str, limit: Option<usize>) -> Result<()> {
+    let mut url = format!("/api/v1/geocoding/lookup?q={}", urlencoding::encode(query));
+    if let Some(limit) = limit {
+        url.push_str(&format!("&limit={}", limit));
+    }
+
+    let response = client.get(&url).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to look up '{}': {} - {}", query, status, body);
+    }
+
+    let age = response
+        .headers()
+        .get(reqwest::header::AGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<i64>().ok());
+
+    let results: Vec<GeocodingResult> = response.json().await?;
+
+    if results.is_empty() {
+        println!("No matches found for '{}'.", query);
+        return Ok(());
+    }
+
+    println!("Matches for '{}':", query);
+    println!();
+    for (index, result) in results.iter().enumerate() {
+        print!("  {}) {} ({}", index + 1, result.city_name, result.country);
+        if let Some(state) = &result.state {
+            print!(", {}", state);
+        }
+        println!(
+            ") - {} [{:.4}, {:.4}]",
+            result.timezone, result.latitude, result.longitude
+        );
+    }
+
+    if let Some(age) = age {
+        println!();
+        println!("(from the server cache, {} old)", describe_age(age));
+    }
+
+    Ok(())
+}
+
+async fn purge(client: &ApiClient, expired: bool) -> Result<()> {
+    let url = if expired {
+        "/api/v1/geocoding/cache?expired=true"
+    } else {
+        "/api/v1/geocoding/cache"
+    };
+    let response = client.delete(url).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to purge geocoding cache: {} - {}", status, body);
+    }
+
+    let purged: PurgeGeocodingCacheResponse = response.json().await?;
+    println!("Purged {} cached geocoding lookup(s).", purged.purged);
+
+    Ok(())
+}
+
+/// Describe an age in seconds, e.g. `40s`, `12m` or `3h`
+fn describe_age(seconds: i64) -> String {
+    match seconds {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86_400),
+    }
+}
@@ -1,16 +1,35 @@
 use anyhow::{Context, Result};
 use lib::config::CliConfig;
 use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
 use uuid::Uuid;
 
-use crate::{client::ApiClient, LocationCommands};
+use crate::{client::ApiClient, LocationCommands, LocationGroupCommands};
 
 #[derive(Debug, Serialize)]
 struct CreateLocationRequest {
     name: String,
     timezone: String,
-    latitude: Option<f32>,
-    longitude: Option<f32>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct UpdateLocationRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timezone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latitude: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    longitude: Option<f64>,
+    derive_timezone: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct SetDefaultLocationRequest {
+    location_id: Uuid,
 }
 
 #[derive(Debug, Deserialize)]
@@ -18,31 +37,197 @@ struct LocationResponse {
     id: Uuid,
     name: String,
     timezone: String,
-    latitude: Option<f32>,
-    longitude: Option<f32>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateLocationGroupRequest {
+    name: String,
+    location_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+struct SetGroupLocationsRequest {
+    location_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+struct AddGroupLocationRequest {
+    location_id: Uuid,
+    position: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocationGroupResponse {
+    name: String,
+    locations: Vec<LocationResponse>,
 }
 
 #[derive(Debug, Deserialize)]
 struct LocationTimeResponse {
     location: LocationResponse,
     formatted_time: String,
+    utc_offset: String,
+    is_dst: bool,
+    offset_from_default_seconds: Option<i32>,
+}
+
+impl LocationTimeResponse {
+    /// `2026-10-18 16:49:47 CEST (UTC+02:00, DST, +6h)`
+    fn describe(&self) -> String {
+        let mut details = vec![format!("UTC{}", self.utc_offset)];
+        if self.is_dst {
+            details.push("DST".to_string());
+        }
+        if let Some(offset) = self.offset_from_default_seconds {
+            details.push(describe_offset(offset));
+        }
+
+        format!("{} ({})", self.formatted_time, details.join(", "))
+    }
+}
+
+/// Describe an offset difference, e.g. `+6h`, `-4h 30m` or `same time`
+fn describe_offset(seconds: i32) -> String {
+    if seconds == 0 {
+        return "same time".to_string();
+    }
+
+    let sign = if seconds > 0 { '+' } else { '-' };
+    let minutes = seconds.abs() / 60;
+
+    if minutes % 60 == 0 {
+        format!("{}{}h", sign, minutes / 60)
+    } else {
+        format!("{}{}h {}m", sign, minutes / 60, minutes % 60)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResult {
+    city_name: String,
+    country: String,
+    state: Option<String>,
+    latitude: f64,
+    longitude: f64,
+    timezone: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReverseGeocodingResult {
+    timezone: String,
+    place: Option<GeocodingResult>,
+    distance_km: Option<f64>,
+}
+
+impl GeocodingResult {
+    /// `Portland (US, Oregon) - America/Los_Angeles`
+    fn describe(&self) -> String {
+        format!(
+            "{} ({}{}) - {}",
+            self.city_name,
+            self.country,
+            self.state
+                .as_ref()
+                .map(|s| format!(", {}", s))
+                .unwrap_or_default(),
+            self.timezone
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ConvertedTime {
+    location: LocationResponse,
+    formatted_time: String,
+    alternative_formatted_time: Option<String>,
 }
 
-pub async fn handle(command: LocationCommands, config: &CliConfig) -> Result<()> {
+#[derive(Debug, Deserialize)]
+struct ConvertTimeResponse {
+    from_timezone: String,
+    status: String,
+    conversions: Vec<ConvertedTime>,
+}
+
+pub async fn handle(command: LocationCommands, config: &mut CliConfig) -> Result<()> {
     let client = ApiClient::new(config);
 
     match command {
-        LocationCommands::Add { name, tz, lat, lon } => {
-            add_location(&client, name, tz, lat, lon).await?;
+        LocationCommands::Add {
+            name,
+            tz,
+            lat,
+            lon,
+            pick,
+        } => {
+            add_location(&client, name, tz, lat, lon, pick).await?;
+        }
+        LocationCommands::List { group } => {
+            list_locations(&client, group).await?;
+        }
+        LocationCommands::Group { command } => {
+            handle_group(&client, command).await?;
         }
-        LocationCommands::List => {
-            list_locations(&client).await?;
+        LocationCommands::Edit {
+            name,
+            rename,
+            tz,
+            lat,
+            lon,
+            auto_tz,
+        } => {
+            let req = UpdateLocationRequest {
+                name: rename,
+                timezone: tz,
+                latitude: lat,
+                longitude: lon,
+                derive_timezone: auto_tz,
+            };
+            edit_location(&client, name, req).await?;
         }
-        LocationCommands::Time { name } => {
-            show_times(&client, name).await?;
+        LocationCommands::Rename { name, new_name } => {
+            let req = UpdateLocationRequest {
+                name: Some(new_name),
+                ..Default::default()
+            };
+            edit_location(&client, name, req).await?;
         }
-        LocationCommands::WatchAll => {
-            watch_all_locations(config).await?;
+        LocationCommands::Rm { name } => {
+            remove_location(&client, name).await?;
+        }
+        LocationCommands::Time { name, all, group } => {
+            let name = match (name, all || group.is_some()) {
+                (Some(name), _) => Some(name),
+                (None, false) => current_location_name(&client, config).await?,
+                (None, true) => None,
+            };
+            show_times(&client, config, name, group).await?;
+        }
+        LocationCommands::Current => {
+            show_current_location(&client, config).await?;
+        }
+        LocationCommands::SetDefault { name, local } => {
+            set_default_location(&client, config, name, local).await?;
+        }
+        LocationCommands::ClearDefault { local } => {
+            clear_default_location(&client, config, local).await?;
+        }
+        LocationCommands::Convert { time, from, to } => {
+            convert_time(&client, config, time, from, to).await?;
+        }
+        LocationCommands::Plan {
+            names,
+            date,
+            start,
+            end,
+            hours,
+        } => {
+            plan_meeting(&client, config, names, date, start, end, hours).await?;
+        }
+        LocationCommands::WatchAll { group } => {
+            watch_all_locations(config, group).await?;
         }
     }
 
@@ -51,17 +236,51 @@ pub async fn handle(command: LocationCommands, config: &CliConfig) -> Result<()>
 
 async fn add_location(
     client: &ApiClient,
-    name: String,
+    name: Option<String>,
     tz: Option<String>,
-    lat: Option<f32>,
-    lon: Option<f32>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    pick: Option<usize>,
 ) -> Result<()> {
-    let (timezone, latitude, longitude) = match (tz, lat, lon) {
-        (Some(tz), lat, lon) => {
+    let (name, timezone, latitude, longitude) = match (name, tz, lat, lon) {
+        (Some(name), Some(tz), lat, lon) => {
             // User provided timezone, use as-is
-            (tz, lat, lon)
+            (name, tz, lat, lon)
         }
-        (None, _, _) => {
+        (name, None, Some(lat), Some(lon)) => {
+            // Derive the timezone from the coordinates and suggest a name
+            println!("Looking up location data for {}, {}...", lat, lon);
+
+            let url = format!("/api/v1/geocoding/reverse?lat={}&lon={}", lat, lon);
+            let response = client.get(&url).await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await?;
+                anyhow::bail!(
+                    "Failed to lookup location: {} - {}\nTry providing --tz manually",
+                    status,
+                    body
+                );
+            }
+
+            let reverse: ReverseGeocodingResult = response.json().await?;
+
+            if let Some(place) = &reverse.place {
+                let distance = reverse.distance_km.unwrap_or_default();
+                println!("Nearest place: {} ({:.0} km away)", place.describe(), distance);
+            }
+            println!("Timezone: {}", reverse.timezone);
+
+            let suggestion = reverse.place.map(|place| place.city_name);
+            let name = match name {
+                Some(name) => name,
+                None => prompt_name(suggestion)?,
+            };
+
+            (name, reverse.timezone, Some(lat), Some(lon))
+        }
+        (Some(name), None, _, _) => {
             // Auto-geocode the location
             println!("Looking up location data for '{}'...", name);
 
@@ -74,24 +293,15 @@ async fn add_location(
                 anyhow::bail!("Failed to lookup location: {} - {}\nTry providing --tz, --lat, and --lon manually", status, body);
             }
 
-            #[derive(Deserialize)]
-            struct GeocodingResult {
-                city_name: String,
-                country: String,
-                state: Option<String>,
-                latitude: f32,
-                longitude: f32,
-                timezone: String,
-            }
-
-            let geo: GeocodingResult = response.json().await?;
+            let candidates: Vec<GeocodingResult> = response.json().await?;
+            let geo = choose_candidate(candidates, pick)?;
 
-            println!("Found: {} ({}{})",
-                geo.city_name,
-                geo.country,
-                geo.state.map(|s| format!(", {}", s)).unwrap_or_default());
+            println!("Found: {}", geo.describe());
 
-            (geo.timezone, Some(geo.latitude), Some(geo.longitude))
+            (name, geo.timezone, Some(geo.latitude), Some(geo.longitude))
+        }
+        (None, ..) => {
+            anyhow::bail!("Provide a location name, or --lat and --lon to suggest one");
         }
     };
 
@@ -123,8 +333,84 @@ async fn add_location(
     Ok(())
 }
 
-async fn list_locations(client: &ApiClient) -> Result<()> {
-    let response = client.get("/api/v1/locations").await?;
+/// Ask for a location name, defaulting to the suggestion on empty input
+fn prompt_name(suggestion: Option<String>) -> Result<String> {
+    match &suggestion {
+        Some(suggestion) => print!("Location name [{}]: ", suggestion),
+        None => print!("Location name: "),
+    }
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    match (input.trim(), suggestion) {
+        ("", Some(suggestion)) => Ok(suggestion),
+        ("", None) => anyhow::bail!("A location name is required"),
+        (name, _) => Ok(name.to_string()),
+    }
+}
+
+/// Pick one geocoding match: the `--pick` index, the only match, or whatever the user chooses
+fn choose_candidate(
+    mut candidates: Vec<GeocodingResult>,
+    pick: Option<usize>,
+) -> Result<GeocodingResult> {
+    if candidates.is_empty() {
+        anyhow::bail!("No matches found\nTry providing --tz, --lat, and --lon manually");
+    }
+
+    if let Some(pick) = pick {
+        if pick == 0 || pick > candidates.len() {
+            anyhow::bail!("--pick must be between 1 and {}", candidates.len());
+        }
+        return Ok(candidates.swap_remove(pick - 1));
+    }
+
+    if candidates.len() == 1 {
+        return Ok(candidates.swap_remove(0));
+    }
+
+    println!("Multiple matches found:");
+    for (index, candidate) in candidates.iter().enumerate() {
+        println!(
+            "  {}) {} [{:.4}, {:.4}]",
+            index + 1,
+            candidate.describe(),
+            candidate.latitude,
+            candidate.longitude
+        );
+    }
+
+    print!("Select a match (1-{}): ", candidates.len());
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    match input.trim().parse::<usize>() {
+        Ok(choice) if (1..=candidates.len()).contains(&choice) => {
+            Ok(candidates.swap_remove(choice - 1))
+        }
+        _ => anyhow::bail!(
+            "Invalid selection '{}' (use --pick N to choose non-interactively)",
+            input.trim()
+        ),
+    }
+}
+
+async fn list_locations(client: &ApiClient, group: Option<String>) -> Result<()> {
+    let path = match &group {
+        Some(group) => format!("/api/v1/locations?group={}", urlencoding::encode(group)),
+        None => "/api/v1/locations".to_string(),
+    };
+    let response = client.get(&path).await?;
+
+    if let Some(group) = &group {
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!("Location group '{}' not found", group);
+        }
+    }
 
     if !response.status().is_success() {
         let status = response.status();
@@ -153,26 +439,377 @@ async fn list_locations(client: &ApiClient) -> Result<()> {
     Ok(())
 }
 
-async fn show_times(client: &ApiClient, name: Option<String>) -> Result<()> {
+async fn find_location(client: &ApiClient, name: &str) -> Result<LocationResponse> {
+    let response = client.get("/api/v1/locations").await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to get locations: {} - {}", status, body);
+    }
+
+    let locations: Vec<LocationResponse> = response.json().await?;
+    locations
+        .into_iter()
+        .find(|l| l.name == name)
+        .context(format!("Location '{}' not found", name))
+}
+
+/// Look up a location by ID (`None` if it no longer exists)
+async fn get_location(client: &ApiClient, id: Uuid) -> Result<Option<LocationResponse>> {
+    let response = client.get(&format!("/api/v1/locations/{}", id)).await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to get location: {} - {}", status, body);
+    }
+
+    Ok(Some(response.json().await?))
+}
+
+/// Name of the location the local override points at, for display
+pub async fn local_location_label(client: &ApiClient, id: Uuid) -> String {
+    match get_location(client, id).await {
+        Ok(Some(location)) => location.name,
+        Ok(None) => format!("{} (deleted)", id),
+        Err(_) => id.to_string(),
+    }
+}
+
+/// Resolve the current location: the local override if set, otherwise the server default for this token
+async fn current_location(
+    client: &ApiClient,
+    config: &CliConfig,
+) -> Result<Option<LocationResponse>> {
+    if let Some(id) = config.default_location_id {
+        let location = get_location(client, id).await?.context(
+            "The local current location no longer exists \
+             (clear it with: cs location clear-default --local)",
+        )?;
+        return Ok(Some(location));
+    }
+
+    let response = client.get("/api/v1/locations/default").await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to get default location: {} - {}", status, body);
+    }
+
+    Ok(Some(response.json().await?))
+}
+
+/// Name of the current location, if one is set
+pub async fn current_location_name(
+    client: &ApiClient,
+    config: &CliConfig,
+) -> Result<Option<String>> {
+    Ok(current_location(client, config).await?.map(|location| location.name))
+}
+
+async fn show_current_location(client: &ApiClient, config: &CliConfig) -> Result<()> {
+    let Some(location) = current_location(client, config).await? else {
+        println!("No current location set.");
+        println!("\nSet one with: cs set location <name>");
+        return Ok(());
+    };
+
+    let source = if config.default_location_id.is_some() {
+        "local config"
+    } else {
+        "server default"
+    };
+
+    println!("Current location: {} ({})", location.name, source);
+    println!("  ID: {}", location.id);
+    println!("  Timezone: {}", location.timezone);
+    if let (Some(lat), Some(lon)) = (location.latitude, location.longitude) {
+        println!("  Coordinates: {}, {}", lat, lon);
+    }
+
+    Ok(())
+}
+
+async fn set_default_location(
+    client: &ApiClient,
+    config: &mut CliConfig,
+    name: String,
+    local: bool,
+) -> Result<()> {
+    let location = find_location(client, &name).await?;
+
+    if local {
+        config.default_location_id = Some(location.id);
+        config.save()?;
+        println!("Current location set to '{}' (local config).", location.name);
+        return Ok(());
+    }
+
+    let req = SetDefaultLocationRequest {
+        location_id: location.id,
+    };
+    let response = client.put("/api/v1/locations/default", &req).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to set default location: {} - {}", status, body);
+    }
+
+    println!("Current location set to '{}'.", location.name);
+    if let Some(local_id) = config.default_location_id {
+        println!(
+            "Note: the local override '{}' still takes precedence (clear it with: cs location clear-default --local)",
+            local_location_label(client, local_id).await
+        );
+    }
+
+    Ok(())
+}
+
+async fn clear_default_location(client: &ApiClient, config: &mut CliConfig, local: bool) -> Result<()> {
+    if local {
+        config.default_location_id = None;
+        config.save()?;
+        println!("Local current location override cleared.");
+        return Ok(());
+    }
+
+    let response = client.delete("/api/v1/locations/default").await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to clear default location: {} - {}", status, body);
+    }
+
+    println!("Current location cleared.");
+
+    Ok(())
+}
+
+async fn edit_location(client: &ApiClient, name: String, req: UpdateLocationRequest) -> Result<()> {
+    let location = find_location(client, &name).await?;
+
+    let url = format!("/api/v1/locations/{}", location.id);
+    let response = client.patch(&url, &req).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to update location: {} - {}", status, body);
+    }
+
+    let location: LocationResponse = response.json().await?;
+
+    println!("Location updated!");
+    println!("  Name: {}", location.name);
+    println!("  ID: {}", location.id);
+    println!("  Timezone: {}", location.timezone);
+    if let (Some(lat), Some(lon)) = (location.latitude, location.longitude) {
+        println!("  Coordinates: {}, {}", lat, lon);
+    }
+
+    Ok(())
+}
+
+async fn remove_location(client: &ApiClient, name: String) -> Result<()> {
+    let location = find_location(client, &name).await?;
+
+    let url = format!("/api/v1/locations/{}", location.id);
+    let response = client.delete(&url).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to remove location: {} - {}", status, body);
+    }
+
+    let location: LocationResponse = response.json().await?;
+
+    println!("Location '{}' removed.", location.name);
+
+    Ok(())
+}
+
+async fn handle_group(client: &ApiClient, command: LocationGroupCommands) -> Result<()> {
+    match command {
+        LocationGroupCommands::Create { name, locations } => {
+            let req = CreateLocationGroupRequest {
+                name,
+                location_ids: find_location_ids(client, &locations).await?,
+            };
+            let response = client.post("/api/v1/location-groups", &req).await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await?;
+                anyhow::bail!("Failed to create group: {} - {}", status, body);
+            }
+
+            let group: LocationGroupResponse = response.json().await?;
+            println!("Group '{}' created.", group.name);
+            print_group(&group);
+        }
+        LocationGroupCommands::List => {
+            let response = client.get("/api/v1/location-groups").await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await?;
+                anyhow::bail!("Failed to list groups: {} - {}", status, body);
+            }
+
+            let groups: Vec<LocationGroupResponse> = response.json().await?;
+
+            if groups.is_empty() {
+                println!("No location groups found.");
+                return Ok(());
+            }
+
+            println!("Location groups:");
+            for group in groups {
+                println!();
+                println!("  {}", group.name);
+                print_group(&group);
+            }
+        }
+        LocationGroupCommands::Add {
+            group,
+            location,
+            position,
+        } => {
+            let req = AddGroupLocationRequest {
+                location_id: find_location(client, &location).await?.id,
+                position: position.map(|position| position - 1),
+            };
+            let path = format!("/api/v1/location-groups/{}/locations", urlencoding::encode(&group));
+            let response = client.post(&path, &req).await?;
+            let group = group_response(response, &group).await?;
+            println!("Added '{}' to group '{}'.", location, group.name);
+            print_group(&group);
+        }
+        LocationGroupCommands::Remove { group, location } => {
+            let location_id = find_location(client, &location).await?.id;
+            let path = format!(
+                "/api/v1/location-groups/{}/locations/{}",
+                urlencoding::encode(&group),
+                location_id
+            );
+            let response = client.delete(&path).await?;
+            let group = group_response(response, &group).await?;
+            println!("Removed '{}' from group '{}'.", location, group.name);
+            print_group(&group);
+        }
+        LocationGroupCommands::Order { group, locations } => {
+            let req = SetGroupLocationsRequest {
+                location_ids: find_location_ids(client, &locations).await?,
+            };
+            let path = format!("/api/v1/location-groups/{}/locations", urlencoding::encode(&group));
+            let response = client.put(&path, &req).await?;
+            let group = group_response(response, &group).await?;
+            println!("Group '{}' updated.", group.name);
+            print_group(&group);
+        }
+        LocationGroupCommands::Rm { name } => {
+            let path = format!("/api/v1/location-groups/{}", urlencoding::encode(&name));
+            let response = client.delete(&path).await?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                anyhow::bail!("Location group '{}' not found", name);
+            }
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await?;
+                anyhow::bail!("Failed to delete group: {} - {}", status, body);
+            }
+
+            println!("Group '{}' deleted.", name);
+        }
+    }
+
+    Ok(())
+}
+
+async fn group_response(response: reqwest::Response, name: &str) -> Result<LocationGroupResponse> {
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        anyhow::bail!("Location group '{}' not found", name);
+    }
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to update group: {} - {}", status, body);
+    }
+
+    Ok(response.json().await?)
+}
+
+fn print_group(group: &LocationGroupResponse) {
+    if group.locations.is_empty() {
+        println!("    (no locations)");
+    }
+    for (index, location) in group.locations.iter().enumerate() {
+        println!("    {}. {} ({})", index + 1, location.name, location.timezone);
+    }
+}
+
+async fn find_location_ids(client: &ApiClient, names: &[String]) -> Result<Vec<Uuid>> {
+    let mut ids = Vec::new();
+    for name in names {
+        ids.push(find_location(client, name).await?.id);
+    }
+    Ok(ids)
+}
+
+/// Query string for the time endpoints, honouring the configured format and local default location
+fn time_query(config: &CliConfig, group: Option<&str>) -> String {
+    let mut params = Vec::new();
+
+    if let Some(group) = group {
+        params.push(format!("group={}", urlencoding::encode(group)));
+    }
+
+    if let Some(format) = &config.time_format {
+        params.push(format!("format={}", urlencoding::encode(format)));
+    }
+
+    // The server only knows the token's default, so pass the local override explicitly
+    if let Some(id) = config.default_location_id {
+        params.push(format!("relative_to={}", id));
+    }
+
+    if params.is_empty() {
+        return String::new();
+    }
+    format!("?{}", params.join("&"))
+}
+
+async fn show_times(
+    client: &ApiClient,
+    config: &CliConfig,
+    name: Option<String>,
+    group: Option<String>,
+) -> Result<()> {
+    let query = time_query(config, group.as_deref());
+
     match name {
         Some(name) => {
             // Get time for specific location by name
-            // First, get the location by name
-            let locations_response = client.get("/api/v1/locations").await?;
-            if !locations_response.status().is_success() {
-                let status = locations_response.status();
-                let body = locations_response.text().await?;
-                anyhow::bail!("Failed to get locations: {} - {}", status, body);
-            }
-
-            let locations: Vec<LocationResponse> = locations_response.json().await?;
-            let location = locations
-                .iter()
-                .find(|l| l.name == name)
-                .context(format!("Location '{}' not found", name))?;
+            let location = find_location(client, &name).await?;
 
             // Get time for this location
-            let url = format!("/api/v1/locations/{}/time", location.id);
+            let url = format!("/api/v1/locations/{}/time{}", location.id, query);
             let response = client.get(&url).await?;
 
             if !response.status().is_success() {
@@ -182,11 +819,19 @@ async fn show_times(client: &ApiClient, name: Option<String>) -> Result<()> {
             }
 
             let time_response: LocationTimeResponse = response.json().await?;
-            println!("{}: {}", time_response.location.name, time_response.formatted_time);
+            println!("{}: {}", time_response.location.name, time_response.describe());
         }
         None => {
             // Get times for all locations
-            let response = client.get("/api/v1/locations/times").await?;
+            let response = client
+                .get(&format!("/api/v1/locations/times{}", query))
+                .await?;
+
+            if let Some(group) = &group {
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    anyhow::bail!("Location group '{}' not found", group);
+                }
+            }
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -204,7 +849,7 @@ async fn show_times(client: &ApiClient, name: Option<String>) -> Result<()> {
             println!("Current times:");
             println!();
             for time in times {
-                println!("  {}: {}", time.location.name, time.formatted_time);
+                println!("  {}: {}", time.location.name, time.describe());
             }
         }
     }
@@ -212,10 +857,124 @@ async fn show_times(client: &ApiClient, name: Option<String>) -> Result<()> {
     Ok(())
 }
 
-async fn watch_all_locations(config: &CliConfig) -> Result<()> {
+async fn convert_time(
+    client: &ApiClient,
+    config: &CliConfig,
+    time: String,
+    from: String,
+    to: Vec<String>,
+) -> Result<()> {
+    // Accept a raw IANA timezone for the source when no location has that name
+    let source = match find_location(client, &from).await {
+        Ok(location) => location.id.to_string(),
+        Err(err) => {
+            if from.parse::<chrono_tz::Tz>().is_err() {
+                return Err(err);
+            }
+            from.clone()
+        }
+    };
+
+    let targets = if to.iter().any(|name| name == "all") {
+        "all".to_string()
+    } else {
+        let mut ids = Vec::new();
+        for name in &to {
+            ids.push(find_location(client, name.trim()).await?.id.to_string());
+        }
+        ids.join(",")
+    };
+
+    let mut path = format!(
+        "/api/v1/locations/convert?time={}&from={}&to={}",
+        urlencoding::encode(&time),
+        urlencoding::encode(&source),
+        targets
+    );
+    if let Some(format) = &config.time_format {
+        path.push_str(&format!("&format={}", urlencoding::encode(format)));
+    }
+    let response = client.get(&path).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to convert time: {} - {}", status, body);
+    }
+
+    let result: ConvertTimeResponse = response.json().await?;
+
+    println!("{} in {} ({}):", time, from, result.from_timezone);
+
+    match result.status.as_str() {
+        "ambiguous" => println!(
+            "  Warning: this time occurs twice because clocks go back; showing both occurrences"
+        ),
+        "nonexistent" => println!(
+            "  Warning: this time does not exist because clocks go forward; shifted past the gap"
+        ),
+        _ => {}
+    }
+
+    if result.conversions.is_empty() {
+        println!("\nNo locations found.");
+        return Ok(());
+    }
+
+    println!();
+    for converted in result.conversions {
+        match converted.alternative_formatted_time {
+            Some(alternative) => println!(
+                "  {}: {} / {}",
+                converted.location.name, converted.formatted_time, alternative
+            ),
+            None => println!("  {}: {}", converted.location.name, converted.formatted_time),
+        }
+    }
+
+    Ok(())
+}
+
+async fn plan_meeting(
+    client: &ApiClient,
+    config: &CliConfig,
+    names: Vec<String>,
+    date: Option<chrono::NaiveDate>,
+    start: String,
+    end: String,
+    hours: Vec<String>,
+) -> Result<()> {
+    use super::super::tui::location_plan::{self, PlanQuery};
+
+    let mut location_ids = Vec::new();
+    for name in &names {
+        location_ids.push(find_location(client, name).await?.id);
+    }
+
+    let mut location_hours = Vec::new();
+    for entry in &hours {
+        let (name, window) = entry
+            .split_once('=')
+            .context(format!("Invalid working hours '{}', expected NAME=HH:MM-HH:MM", entry))?;
+        let location = find_location(client, name).await?;
+        location_hours.push((location.id, window.to_string()));
+    }
+
+    let query = PlanQuery {
+        date: date.unwrap_or_else(|| chrono::Local::now().date_naive()),
+        location_ids,
+        start,
+        end,
+        hours: location_hours,
+    };
+
+    location_plan::run(config, query).await
+}
+
+async fn watch_all_locations(config: &CliConfig, group: Option<String>) -> Result<()> {
     // Import the TUI module
     use super::super::tui::location_watch_all;
 
     // Run the TUI
-    location_watch_all::run(config).await
+    location_watch_all::run(config, group.as_deref()).await
 }
@@ -1,6 +1,8 @@
 pub mod config;
 pub mod timer;
 pub mod location;
+pub mod geocode;
+pub mod team;
 pub mod weather;
 pub mod task;
 pub mod template;
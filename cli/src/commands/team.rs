@@ -0,0 +1,258 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveTime, Utc};
+use lib::config::CliConfig;
+use lib::timezone::parse_time_of_day;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{client::ApiClient, TeamCommands};
+
+#[derive(Debug, Serialize)]
+struct CreateTeamMemberRequest {
+    name: String,
+    email: Option<String>,
+    location_id: Option<Uuid>,
+    timezone: Option<String>,
+    work_start: Option<NaiveTime>,
+    work_end: Option<NaiveTime>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocationResponse {
+    id: Uuid,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamMemberResponse {
+    id: Uuid,
+    name: String,
+    email: Option<String>,
+    location_name: Option<String>,
+    timezone: String,
+    work_start: NaiveTime,
+    work_end: NaiveTime,
+}
+
+impl TeamMemberResponse {
+    /// `Berlin (Europe/Berlin)` or just the timezone for members without a location
+    fn place(&self) -> String {
+        match &self.location_name {
+            Some(location) => format!("{} ({})", location, self.timezone),
+            None => self.timezone.clone(),
+        }
+    }
+
+    /// `09:00-17:00`
+    fn hours(&self) -> String {
+        format!(
+            "{}-{}",
+            self.work_start.format("%H:%M"),
+            self.work_end.format("%H:%M")
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamMemberTimeResponse {
+    member: TeamMemberResponse,
+    formatted_time: String,
+    utc_offset: String,
+    within_working_hours: bool,
+    next_change: Option<DateTime<Utc>>,
+}
+
+impl TeamMemberTimeResponse {
+    /// `working, 2h 15m left` or `off, starts in 9h`
+    fn status(&self) -> String {
+        let until = self
+            .next_change
+            .map(|change| describe_duration((change - Utc::now()).num_minutes()));
+
+        match (self.within_working_hours, until) {
+            (true, Some(until)) => format!("working, {} left", until),
+            (true, None) => "working".to_string(),
+            (false, Some(until)) => format!("off, starts in {}", until),
+            (false, None) => "off".to_string(),
+        }
+    }
+}
+
+/// Describe a number of minutes, e.g. `45m`, `3h` or `2h 15m`
+fn describe_duration(minutes: i64) -> String {
+    let minutes = minutes.max(0);
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+pub async fn handle(command: TeamCommands, config: &CliConfig) -> Result<()> {
+    let client = ApiClient::new(config);
+
+    match command {
+        TeamCommands::List => {
+            list_members(&client).await?;
+        }
+        TeamCommands::Add {
+            name,
+            email,
+            tz,
+            location,
+            start,
+            end,
+        } => {
+            let location_id = match location {
+                Some(location) => Some(find_location_id(&client, &location).await?),
+                None => None,
+            };
+
+            let req = CreateTeamMemberRequest {
+                name,
+                email,
+                location_id,
+                timezone: tz,
+                work_start: start.as_deref().map(parse_time_of_day).transpose()?,
+                work_end: end.as_deref().map(parse_time_of_day).transpose()?,
+            };
+            add_member(&client, req).await?;
+        }
+        TeamCommands::Now => {
+            show_now(&client, config).await?;
+        }
+        TeamCommands::Rm { name } => {
+            remove_member(&client, name).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn add_member(client: &ApiClient, req: CreateTeamMemberRequest) -> Result<()> {
+    let response = client.post("/api/v1/team", &req).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to add team member: {} - {}", status, body);
+    }
+
+    let member: TeamMemberResponse = response.json().await?;
+
+    println!("Team member '{}' added.", member.name);
+    println!("  Works from: {}", member.place());
+    println!("  Hours: {}", member.hours());
+
+    Ok(())
+}
+
+async fn list_members(client: &ApiClient) -> Result<()> {
+    let members = get_members(client).await?;
+
+    if members.is_empty() {
+        println!("No team members found.");
+        return Ok(());
+    }
+
+    println!("Team:");
+    println!();
+    for member in members {
+        print!("  {} - {}", member.id, member.name);
+        if let Some(email) = &member.email {
+            print!(" <{}>", email);
+        }
+        println!(" - {}, {}", member.place(), member.hours());
+    }
+
+    Ok(())
+}
+
+async fn show_now(client: &ApiClient, config: &CliConfig) -> Result<()> {
+    let path = match &config.time_format {
+        Some(format) => format!("/api/v1/team/now?format={}", urlencoding::encode(format)),
+        None => "/api/v1/team/now".to_string(),
+    };
+    let response = client.get(&path).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to get team times: {} - {}", status, body);
+    }
+
+    let times: Vec<TeamMemberTimeResponse> = response.json().await?;
+
+    if times.is_empty() {
+        println!("No team members found.");
+        return Ok(());
+    }
+
+    let width = times
+        .iter()
+        .map(|time| time.member.name.len())
+        .max()
+        .unwrap_or(0);
+    for time in &times {
+        println!(
+            "{:width$}  {} (UTC{}) - {}",
+            time.member.name,
+            time.formatted_time,
+            time.utc_offset,
+            time.status(),
+            width = width
+        );
+    }
+
+    Ok(())
+}
+
+async fn remove_member(client: &ApiClient, name: String) -> Result<()> {
+    let member = get_members(client)
+        .await?
+        .into_iter()
+        .find(|member| member.name == name)
+        .context(format!("Team member '{}' not found", name))?;
+
+    let response = client
+        .delete(&format!("/api/v1/team/{}", member.id))
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to remove team member: {} - {}", status, body);
+    }
+
+    println!("Team member '{}' removed.", member.name);
+
+    Ok(())
+}
+
+async fn get_members(client: &ApiClient) -> Result<Vec<TeamMemberResponse>> {
+    let response = client.get("/api/v1/team").await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to list team members: {} - {}", status, body);
+    }
+
+    Ok(response.json().await?)
+}
+
+async fn find_location_id(client: &ApiClient, name: &str) -> Result<Uuid> {
+    let response = client.get("/api/v1/locations").await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to get locations: {} - {}", status, body);
+    }
+
+    let locations: Vec<LocationResponse> = response.json().await?;
+    locations
+        .into_iter()
+        .find(|l| l.name == name)
+        .map(|l| l.id)
+        .context(format!("Location '{}' not found", name))
+}
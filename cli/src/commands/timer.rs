@@ -19,8 +19,6 @@ struct TimerResponse {
     duration_seconds: i32,
     status: String,
     remaining_seconds: Option<i32>,
-    created_at: DateTime<Utc>,
-    started_at: Option<DateTime<Utc>>,
     expires_at: Option<DateTime<Utc>>,
 }
 
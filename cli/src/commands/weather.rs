@@ -1,8 +1,13 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, Utc};
 use lib::config::CliConfig;
-use serde::Deserialize;
+use lib::weather::air::{AqiCategory, UvRisk};
+use lib::weather::units::{compass_direction, UnitSystem};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::location::current_location_name;
+use crate::tui::weather_watch_all::sparkline;
 use crate::{client::ApiClient, WeatherCommands};
 
 #[derive(Debug, Deserialize)]
@@ -14,50 +19,163 @@ struct LocationResponse {
 #[derive(Debug, Deserialize)]
 struct WeatherResponse {
     location_name: String,
-    temperature_celsius: f32,
-    temperature_fahrenheit: f32,
-    feels_like_celsius: f32,
+    units: UnitSystem,
+    temperature: f32,
+    feels_like: f32,
     humidity: u8,
     description: String,
-    wind_speed_ms: f32,
-    wind_speed_mph: f32,
+    wind_speed: f32,
+    wind_direction_degrees: Option<u16>,
+    pressure: Option<f32>,
+    visibility: Option<f32>,
+    cloud_cover: Option<u8>,
+    sunrise: Option<DateTime<FixedOffset>>,
+    sunset: Option<DateTime<FixedOffset>>,
+    cached_at: DateTime<Utc>,
+    stale: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct AirQualityResponse {
+    location_name: String,
+    pm2_5: f32,
+    pm10: f32,
+    ozone: f32,
+    nitrogen_dioxide: f32,
+    category: AqiCategory,
+    uv_index: Option<f32>,
+    uv_risk: Option<UvRisk>,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateWeatherRuleRequest {
+    location_id: Uuid,
+    kind: &'static str,
+    threshold: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct WeatherRule {
+    id: Uuid,
+    location_name: String,
+    kind: String,
+    threshold: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct WeatherAlert {
+    location_name: String,
+    status: String,
+    message: String,
+    fired_at: DateTime<Utc>,
+    resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HourlyForecastResponse {
+    location_name: String,
+    units: UnitSystem,
+    hours: Vec<HourlyForecastEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HourlyForecastEntry {
+    time: DateTime<FixedOffset>,
+    temperature: f32,
+    precipitation_probability: u8,
+    description: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DailyForecastResponse {
+    location_name: String,
+    units: UnitSystem,
+    days: Vec<DailyForecastEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DailyForecastEntry {
+    date: NaiveDate,
+    temperature_min: f32,
+    temperature_max: f32,
+    precipitation_probability: u8,
+    description: String,
 }
 
 pub async fn handle(command: WeatherCommands, config: &CliConfig) -> Result<()> {
     let client = ApiClient::new(config);
+    let units = config.units.unwrap_or_default();
 
     match command {
-        WeatherCommands::Get { name } => {
-            get_weather(&client, name).await?;
+        WeatherCommands::Get { name, all, group } => {
+            let name = match (name, all || group.is_some()) {
+                (Some(name), _) => Some(name),
+                (None, false) => current_location_name(&client, config).await?,
+                (None, true) => None,
+            };
+            get_weather(&client, name, group, units).await?;
+        }
+        WeatherCommands::Forecast { name, hourly } => {
+            let name = location_or_current(&client, config, name).await?;
+            show_forecast(&client, &name, hourly, units).await?;
         }
-        WeatherCommands::WatchAll => {
-            watch_all_weather(config).await?;
+        WeatherCommands::Air { name } => {
+            let name = location_or_current(&client, config, name).await?;
+            show_air_quality(&client, &name).await?;
+        }
+        WeatherCommands::History { name, days, daily } => {
+            if days < 1 {
+                anyhow::bail!("--days must be at least 1");
+            }
+            let name = location_or_current(&client, config, name).await?;
+            let location = find_location(&client, &name).await?;
+
+            use super::super::tui::weather_history;
+            weather_history::run(config, location.id, days, daily).await?;
+        }
+        WeatherCommands::Alerts { all } => {
+            show_alerts(&client, all).await?;
+        }
+        WeatherCommands::Rules { name } => {
+            list_rules(&client, name).await?;
+        }
+        WeatherCommands::AddRule {
+            name,
+            temp_below,
+            wind_above,
+            rain_within,
+        } => {
+            let (kind, threshold) = match (temp_below, wind_above, rain_within) {
+                (Some(threshold), _, _) => ("temperature_below", threshold),
+                (_, Some(threshold), _) => ("wind_above", threshold),
+                (_, _, Some(threshold)) => ("rain_within", threshold),
+                _ => anyhow::bail!("Give --temp-below, --wind-above or --rain-within"),
+            };
+            add_rule(&client, &name, kind, threshold).await?;
+        }
+        WeatherCommands::RmRule { id } => {
+            remove_rule(&client, &id).await?;
+        }
+        WeatherCommands::WatchAll { group } => {
+            watch_all_weather(config, group).await?;
         }
     }
 
     Ok(())
 }
 
-async fn get_weather(client: &ApiClient, name: Option<String>) -> Result<()> {
+async fn get_weather(
+    client: &ApiClient,
+    name: Option<String>,
+    group: Option<String>,
+    units: UnitSystem,
+) -> Result<()> {
     match name {
         Some(name) => {
-            // Get weather for specific location by name
-            // First, get the location by name
-            let locations_response = client.get("/api/v1/locations").await?;
-            if !locations_response.status().is_success() {
-                let status = locations_response.status();
-                let body = locations_response.text().await?;
-                anyhow::bail!("Failed to get locations: {} - {}", status, body);
-            }
-
-            let locations: Vec<LocationResponse> = locations_response.json().await?;
-            let location = locations
-                .iter()
-                .find(|l| l.name == name)
-                .context(format!("Location '{}' not found", name))?;
+            let location = find_location(client, &name).await?;
 
             // Get weather for this location
-            let url = format!("/api/v1/weather/locations/{}", location.id);
+            let url = format!("/api/v1/weather/locations/{}?units={}", location.id, units);
             let response = client.get(&url).await?;
 
             if !response.status().is_success() {
@@ -70,8 +188,22 @@ async fn get_weather(client: &ApiClient, name: Option<String>) -> Result<()> {
             print_weather(&weather);
         }
         None => {
-            // Get weather for all locations
-            let response = client.get("/api/v1/weather/locations").await?;
+            // Get weather for all locations (or a group)
+            let path = match &group {
+                Some(group) => format!(
+                    "/api/v1/weather/locations?group={}&units={}",
+                    urlencoding::encode(group),
+                    units
+                ),
+                None => format!("/api/v1/weather/locations?units={}", units),
+            };
+            let response = client.get(&path).await?;
+
+            if let Some(group) = &group {
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    anyhow::bail!("Location group '{}' not found", group);
+                }
+            }
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -99,23 +231,302 @@ async fn get_weather(client: &ApiClient, name: Option<String>) -> Result<()> {
     Ok(())
 }
 
+async fn show_air_quality(client: &ApiClient, name: &str) -> Result<()> {
+    let location = find_location(client, name).await?;
+    let url = format!("/api/v1/weather/locations/{}/air-quality", location.id);
+    let response = client.get(&url).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to get air quality: {} - {}", status, body);
+    }
+
+    let air: AirQualityResponse = response.json().await?;
+    println!("{}:", air.location_name);
+    println!("  Air quality: {}", air.category);
+    println!("  PM2.5: {:.1} µg/m³", air.pm2_5);
+    println!("  PM10: {:.1} µg/m³", air.pm10);
+    println!("  Ozone: {:.1} µg/m³", air.ozone);
+    println!("  NO2: {:.1} µg/m³", air.nitrogen_dioxide);
+    match (air.uv_index, air.uv_risk) {
+        (Some(index), Some(risk)) => println!("  UV index: {:.1} ({})", index, risk),
+        _ => println!("  UV index: not available from this weather provider"),
+    }
+
+    Ok(())
+}
+
+async fn show_alerts(client: &ApiClient, all: bool) -> Result<()> {
+    let path = if all {
+        "/api/v1/weather/alerts"
+    } else {
+        "/api/v1/weather/alerts?status=firing"
+    };
+    let response = client.get(path).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to get weather alerts: {} - {}", status, body);
+    }
+
+    let alerts: Vec<WeatherAlert> = response.json().await?;
+
+    if alerts.is_empty() {
+        println!("No {}weather alerts.", if all { "" } else { "firing " });
+        return Ok(());
+    }
+
+    for alert in alerts {
+        let fired = alert.fired_at.with_timezone(&Local).format("%Y-%m-%d %H:%M");
+        print!("[{}] {}: {} (since {}", alert.status, alert.location_name, alert.message, fired);
+        if let Some(resolved_at) = alert.resolved_at {
+            print!(", resolved {}", resolved_at.with_timezone(&Local).format("%Y-%m-%d %H:%M"));
+        }
+        println!(")");
+    }
+
+    Ok(())
+}
+
+async fn list_rules(client: &ApiClient, name: Option<String>) -> Result<()> {
+    let path = match &name {
+        Some(name) => {
+            let location = find_location(client, name).await?;
+            format!("/api/v1/weather/rules?location_id={}", location.id)
+        }
+        None => "/api/v1/weather/rules".to_string(),
+    };
+    let response = client.get(&path).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to get weather rules: {} - {}", status, body);
+    }
+
+    let rules: Vec<WeatherRule> = response.json().await?;
+
+    if rules.is_empty() {
+        println!("No weather rules.");
+        println!("Add one with: cs weather add-rule <location> --temp-below 0");
+        return Ok(());
+    }
+
+    for rule in rules {
+        println!("{}  {}: {}", rule.id, rule.location_name, describe_rule(&rule));
+    }
+
+    Ok(())
+}
+
+async fn add_rule(
+    client: &ApiClient,
+    name: &str,
+    kind: &'static str,
+    threshold: f32,
+) -> Result<()> {
+    let location = find_location(client, name).await?;
+    let req = CreateWeatherRuleRequest {
+        location_id: location.id,
+        kind,
+        threshold,
+    };
+    let response = client.post("/api/v1/weather/rules", &req).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to add weather rule: {} - {}", status, body);
+    }
+
+    let rule: WeatherRule = response.json().await?;
+    println!("Added rule for {}: {}", rule.location_name, describe_rule(&rule));
+    println!("  ID: {}", rule.id);
+
+    Ok(())
+}
+
+async fn remove_rule(client: &ApiClient, id: &str) -> Result<()> {
+    let response = client.delete(&format!("/api/v1/weather/rules/{}", id)).await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        anyhow::bail!("Weather rule '{}' not found", id);
+    }
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to remove weather rule: {} - {}", status, body);
+    }
+
+    let rule: WeatherRule = response.json().await?;
+    println!("Removed rule for {}: {}", rule.location_name, describe_rule(&rule));
+
+    Ok(())
+}
+
+fn describe_rule(rule: &WeatherRule) -> String {
+    match rule.kind.as_str() {
+        "temperature_below" => format!("temperature below {}C", rule.threshold),
+        "wind_above" => format!("wind above {} m/s", rule.threshold),
+        "rain_within" => format!("rain expected within {}h", rule.threshold),
+        kind => format!("{} {}", kind, rule.threshold),
+    }
+}
+
+/// The given location name, or the current location if none was given
+async fn location_or_current(
+    client: &ApiClient,
+    config: &CliConfig,
+    name: Option<String>,
+) -> Result<String> {
+    match name {
+        Some(name) => Ok(name),
+        None => current_location_name(client, config)
+            .await?
+            .context("No location given and no current location set"),
+    }
+}
+
+/// Look up a location by name
+async fn find_location(client: &ApiClient, name: &str) -> Result<LocationResponse> {
+    let response = client.get("/api/v1/locations").await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to get locations: {} - {}", status, body);
+    }
+
+    let locations: Vec<LocationResponse> = response.json().await?;
+    locations
+        .into_iter()
+        .find(|l| l.name == name)
+        .context(format!("Location '{}' not found", name))
+}
+
+async fn show_forecast(
+    client: &ApiClient,
+    name: &str,
+    hourly: bool,
+    units: UnitSystem,
+) -> Result<()> {
+    let location = find_location(client, name).await?;
+    let kind = if hourly { "hourly" } else { "daily" };
+    let url = format!(
+        "/api/v1/weather/locations/{}/forecast/{}?units={}",
+        location.id, kind, units
+    );
+    let response = client.get(&url).await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        anyhow::bail!("Failed to get forecast: {} - {}", status, body);
+    }
+
+    if hourly {
+        let forecast: HourlyForecastResponse = response.json().await?;
+        print_hourly_forecast(&forecast);
+    } else {
+        let forecast: DailyForecastResponse = response.json().await?;
+        print_daily_forecast(&forecast);
+    }
+
+    Ok(())
+}
+
+fn print_daily_forecast(forecast: &DailyForecastResponse) {
+    let unit = forecast.units.labels().temperature;
+    println!("{}-day forecast for {}:", forecast.days.len(), forecast.location_name);
+    println!();
+    println!("  {:<10} {:>6} {:>6} {:>5}  Conditions", "Day", "Low", "High", "Rain");
+    for day in &forecast.days {
+        println!(
+            "  {:<10} {:>5.1}{} {:>5.1}{} {:>4}%  {}",
+            day.date.format("%a %d %b"),
+            day.temperature_min,
+            unit,
+            day.temperature_max,
+            unit,
+            day.precipitation_probability,
+            day.description
+        );
+    }
+}
+
+fn print_hourly_forecast(forecast: &HourlyForecastResponse) {
+    let unit = forecast.units.labels().temperature;
+    println!("Hourly forecast for {}:", forecast.location_name);
+    println!();
+    println!("  {:<9} {:>6} {:>5}  Conditions", "Time", "Temp", "Rain");
+    for hour in &forecast.hours {
+        println!(
+            "  {:<9} {:>5.1}{} {:>4}%  {}",
+            hour.time.format("%a %H:%M"),
+            hour.temperature,
+            unit,
+            hour.precipitation_probability,
+            hour.description
+        );
+    }
+
+    let temperatures: Vec<f32> = forecast.hours.iter().map(|h| h.temperature).collect();
+    if let Some((min, max)) = range(&temperatures) {
+        println!();
+        println!("  {}  {:.1}{} to {:.1}{}", sparkline(&temperatures), min, unit, max, unit);
+    }
+}
+
+/// Lowest and highest value, if there are any
+fn range(values: &[f32]) -> Option<(f32, f32)> {
+    let min = values.iter().copied().reduce(f32::min)?;
+    let max = values.iter().copied().reduce(f32::max)?;
+    Some((min, max))
+}
+
 fn print_weather(weather: &WeatherResponse) {
+    let labels = weather.units.labels();
     println!("{}:", weather.location_name);
-    println!("  Temperature: {:.1}C / {:.1}F",
-        weather.temperature_celsius,
-        weather.temperature_fahrenheit);
-    println!("  Feels like: {:.1}C", weather.feels_like_celsius);
+    println!("  Temperature: {:.1}{}", weather.temperature, labels.temperature);
+    println!("  Feels like: {:.1}{}", weather.feels_like, labels.temperature);
     println!("  Conditions: {}", weather.description);
     println!("  Humidity: {}%", weather.humidity);
-    println!("  Wind: {:.1} m/s ({:.1} mph)",
-        weather.wind_speed_ms,
-        weather.wind_speed_mph);
+    match weather.wind_direction_degrees {
+        Some(degrees) => println!(
+            "  Wind: {:.1} {} from {}",
+            weather.wind_speed,
+            labels.speed,
+            compass_direction(degrees)
+        ),
+        None => println!("  Wind: {:.1} {}", weather.wind_speed, labels.speed),
+    }
+    if let Some(pressure) = weather.pressure {
+        // inHg needs decimals; hPa and Pa do not
+        let precision = if weather.units == UnitSystem::Imperial { 2 } else { 0 };
+        println!("  Pressure: {:.*} {}", precision, pressure, labels.pressure);
+    }
+    if let Some(visibility) = weather.visibility {
+        let precision = if weather.units == UnitSystem::Si { 0 } else { 1 };
+        println!("  Visibility: {:.*} {}", precision, visibility, labels.distance);
+    }
+    if let Some(cloud_cover) = weather.cloud_cover {
+        println!("  Cloud cover: {}%", cloud_cover);
+    }
+    if let (Some(sunrise), Some(sunset)) = (weather.sunrise, weather.sunset) {
+        println!("  Sun: up {}, down {}", sunrise.format("%H:%M"), sunset.format("%H:%M"));
+    }
+    println!(
+        "  Updated: {}{}",
+        weather.cached_at.with_timezone(&Local).format("%H:%M"),
+        if weather.stale { " (refreshing)" } else { "" }
+    );
 }
 
-async fn watch_all_weather(config: &CliConfig) -> Result<()> {
+async fn watch_all_weather(config: &CliConfig, group: Option<String>) -> Result<()> {
     // Import the TUI module
     use super::super::tui::weather_watch_all;
 
     // Run the TUI
-    weather_watch_all::run(config).await
+    weather_watch_all::run(config, group.as_deref()).await
 }
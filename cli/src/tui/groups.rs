@@ -0,0 +1,92 @@
+use crossterm::event::KeyCode;
+use lib::config::CliConfig;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct LocationGroupResponse {
+    name: String,
+}
+
+/// Cycles through location groups in the watch TUIs (`None` shows all locations)
+pub struct GroupSelector {
+    groups: Vec<String>,
+    selected: Option<usize>,
+}
+
+impl GroupSelector {
+    /// Load the group names from the server, starting on `initial` if it exists
+    pub async fn load(config: &CliConfig, initial: Option<&str>) -> Self {
+        let client = reqwest::Client::new();
+        let url = format!("{}/api/v1/location-groups", config.server_url);
+
+        let groups: Vec<String> = match client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", config.api_token))
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_success() => response
+                .json::<Vec<LocationGroupResponse>>()
+                .await
+                .map(|groups| groups.into_iter().map(|group| group.name).collect())
+                .unwrap_or_default(),
+            _ => vec![],
+        };
+
+        let selected = initial.and_then(|name| groups.iter().position(|group| group == name));
+
+        Self { groups, selected }
+    }
+
+    /// Handle a group-switching key, returning true if the selection changed
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        let count = self.groups.len();
+        if count == 0 {
+            return false;
+        }
+
+        let selected = match code {
+            KeyCode::Tab => match self.selected {
+                None => Some(0),
+                Some(index) if index + 1 < count => Some(index + 1),
+                Some(_) => None,
+            },
+            KeyCode::BackTab => match self.selected {
+                None => Some(count - 1),
+                Some(0) => None,
+                Some(index) => Some(index - 1),
+            },
+            KeyCode::Char('a') => None,
+            _ => return false,
+        };
+
+        let changed = selected != self.selected;
+        self.selected = selected;
+        changed
+    }
+
+    /// Name of the selected group
+    pub fn current(&self) -> Option<&str> {
+        self.selected.map(|index| self.groups[index].as_str())
+    }
+
+    /// Title label for the selection
+    pub fn label(&self) -> String {
+        match self.current() {
+            Some(name) => format!("Group: {}", name),
+            None => "All locations".to_string(),
+        }
+    }
+
+    /// Query string filtering an endpoint by the selected group
+    pub fn query(&self) -> String {
+        match self.current() {
+            Some(name) => format!("group={}", urlencoding::encode(name)),
+            None => String::new(),
+        }
+    }
+
+    pub fn has_groups(&self) -> bool {
+        !self.groups.is_empty()
+    }
+}
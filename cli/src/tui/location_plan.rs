@@ -0,0 +1,312 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use crossterm::{
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use lib::config::CliConfig;
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Terminal,
+};
+use serde::Deserialize;
+use std::io;
+use uuid::Uuid;
+
+/// Parameters for `GET /api/v1/locations/overlap`
+pub struct PlanQuery {
+    pub date: NaiveDate,
+    pub location_ids: Vec<Uuid>,
+    pub start: String,
+    pub end: String,
+    /// Per-location working hours as `HH:MM-HH:MM`
+    pub hours: Vec<(Uuid, String)>,
+}
+
+impl PlanQuery {
+    fn path(&self) -> String {
+        let mut path = format!(
+            "/api/v1/locations/overlap?date={}&start={}&end={}",
+            self.date,
+            urlencoding::encode(&self.start),
+            urlencoding::encode(&self.end)
+        );
+
+        if !self.location_ids.is_empty() {
+            let ids: Vec<String> = self.location_ids.iter().map(Uuid::to_string).collect();
+            path.push_str(&format!("&locations={}", ids.join(",")));
+        }
+
+        if !self.hours.is_empty() {
+            let hours: Vec<String> = self
+                .hours
+                .iter()
+                .map(|(id, window)| format!("{}={}", id, window))
+                .collect();
+            path.push_str(&format!("&hours={}", urlencoding::encode(&hours.join(","))));
+        }
+
+        path
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LocationResponse {
+    name: String,
+    timezone: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Interval {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocationWorkingHours {
+    location: LocationResponse,
+    windows: Vec<Interval>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocalSlot {
+    name: String,
+    start: String,
+    end: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OverlapSlot {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    duration_minutes: i64,
+    local: Vec<LocalSlot>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OverlapResponse {
+    date: NaiveDate,
+    reference_timezone: String,
+    day_start: DateTime<Utc>,
+    day_end: DateTime<Utc>,
+    locations: Vec<LocationWorkingHours>,
+    slots: Vec<OverlapSlot>,
+}
+
+pub async fn run(config: &CliConfig, query: PlanQuery) -> Result<()> {
+    // Setup terminal
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    // Run the app
+    let res = run_app(&mut terminal, config, query).await;
+
+    // Restore terminal
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    if let Err(err) = res {
+        println!("Error: {:?}", err);
+    }
+
+    Ok(())
+}
+
+async fn fetch_plan(config: &CliConfig, query: &PlanQuery) -> Result<OverlapResponse, String> {
+    let client = reqwest::Client::new();
+    let url = format!("{}{}", config.server_url, query.path());
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", config.api_token))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("Server returned {}", response.status()));
+    }
+
+    response.json().await.map_err(|e| e.to_string())
+}
+
+async fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    config: &CliConfig,
+    mut query: PlanQuery,
+) -> Result<()> {
+    let mut plan = fetch_plan(config, &query).await;
+
+    loop {
+        terminal.draw(|f| {
+            let size = f.area();
+
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(2)
+                .constraints(
+                    [
+                        Constraint::Length(3),
+                        Constraint::Min(1),
+                        Constraint::Length(8),
+                        Constraint::Length(1),
+                    ]
+                    .as_ref(),
+                )
+                .split(size);
+
+            // Title
+            let title = match &plan {
+                Ok(plan) => format!(
+                    "Meeting Planner - {} ({})",
+                    plan.date, plan.reference_timezone
+                ),
+                Err(_) => format!("Meeting Planner - {}", query.date),
+            };
+            let title = Paragraph::new(title)
+                .style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL));
+            f.render_widget(title, chunks[0]);
+
+            match &plan {
+                Err(err) => {
+                    let error = Paragraph::new(format!("Failed to load plan: {}", err))
+                        .style(Style::default().fg(Color::Red))
+                        .alignment(Alignment::Center)
+                        .block(Block::default().borders(Borders::ALL));
+                    f.render_widget(error, chunks[1]);
+                }
+                Ok(plan) => {
+                    f.render_widget(hour_grid(plan), chunks[1]);
+                    f.render_widget(slot_list(plan), chunks[2]);
+                }
+            }
+
+            // Help text at bottom
+            let help = Paragraph::new("←/→ change day | 't' today | 'q' quit")
+                .style(Style::default().fg(Color::DarkGray))
+                .alignment(Alignment::Center);
+            f.render_widget(help, chunks[3]);
+        })?;
+
+        if let Event::Key(key) = event::read()? {
+            let date = match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Left => query.date - Duration::days(1),
+                KeyCode::Right => query.date + Duration::days(1),
+                KeyCode::Char('t') => chrono::Local::now().date_naive(),
+                _ => continue,
+            };
+
+            if date != query.date {
+                query.date = date;
+                plan = fetch_plan(config, &query).await;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// One row per location, one column per hour of the planned day, showing local hours
+fn hour_grid(plan: &OverlapResponse) -> Table<'_> {
+    let hours = (plan.day_end - plan.day_start).num_hours().max(0);
+    let columns: Vec<DateTime<Utc>> = (0..hours)
+        .map(|hour| plan.day_start + Duration::hours(hour))
+        .collect();
+
+    // A cell covers the hour [at, at + 1h), so it is marked if any part of that hour is in an
+    // interval (windows and slots need not start on the hour)
+    let intersects = |start: DateTime<Utc>, end: DateTime<Utc>, at: DateTime<Utc>| {
+        start < at + Duration::hours(1) && at < end
+    };
+    let within = |intervals: &[Interval], at: DateTime<Utc>| {
+        intervals.iter().any(|i| intersects(i.start, i.end, at))
+    };
+    let in_overlap = |at: DateTime<Utc>| plan.slots.iter().any(|s| intersects(s.start, s.end, at));
+
+    let rows: Vec<Row> = plan
+        .locations
+        .iter()
+        .map(|planned| {
+            let tz: Option<Tz> = planned.location.timezone.parse().ok();
+            let mut cells = vec![Cell::from(planned.location.name.clone())];
+
+            cells.extend(columns.iter().map(|&at| {
+                let label = match tz {
+                    Some(tz) => at.with_timezone(&tz).format("%H").to_string(),
+                    None => "??".to_string(),
+                };
+                let style = if in_overlap(at) {
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Green)
+                        .add_modifier(Modifier::BOLD)
+                } else if within(&planned.windows, at) {
+                    Style::default().fg(Color::Black).bg(Color::Blue)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                Cell::from(label).style(style)
+            }));
+
+            Row::new(cells)
+        })
+        .collect();
+
+    let mut widths = vec![Constraint::Length(16)];
+    widths.extend(columns.iter().map(|_| Constraint::Length(2)));
+
+    Table::new(rows, widths).column_spacing(1).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Local hours (green = everyone working, blue = working)"),
+    )
+}
+
+/// The overlapping slots in each location's local time
+fn slot_list(plan: &OverlapResponse) -> Paragraph<'_> {
+    let lines: Vec<Line> = if plan.slots.is_empty() {
+        vec![Line::styled(
+            "No overlapping working hours on this day",
+            Style::default().fg(Color::Yellow),
+        )]
+    } else {
+        plan.slots
+            .iter()
+            .flat_map(|slot| {
+                let mut lines = vec![Line::styled(
+                    format!("{} minutes", slot.duration_minutes),
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                )];
+                lines.extend(slot.local.iter().map(|local| {
+                    Line::from(format!("  {}: {} - {}", local.name, local.start, local.end))
+                }));
+                lines
+            })
+            .collect()
+    };
+
+    Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Overlap"))
+}
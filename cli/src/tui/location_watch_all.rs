@@ -12,25 +12,65 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Row, Table},
     Terminal,
 };
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::Deserialize;
 use std::io;
-use tokio::time::{sleep, Duration};
-use uuid::Uuid;
+use std::time::Duration;
+
+use super::groups::GroupSelector;
 
 #[derive(Debug, Deserialize)]
 struct LocationResponse {
-    id: Uuid,
     name: String,
     timezone: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct SolarTimes {
+    civil_dawn: Option<DateTime<Utc>>,
+    sunrise: Option<DateTime<Utc>>,
+    sunset: Option<DateTime<Utc>>,
+    civil_dusk: Option<DateTime<Utc>>,
+    day_length_seconds: i64,
+}
+
 #[derive(Debug, Deserialize)]
 struct LocationTimeResponse {
     location: LocationResponse,
     formatted_time: String,
+    solar: Option<SolarTimes>,
+}
+
+/// Format a sun event as local `HH:MM`, or `--` if it does not happen that day
+fn local_hm(time: Option<DateTime<Utc>>, timezone: &str) -> String {
+    match (time, timezone.parse::<Tz>()) {
+        (Some(time), Ok(tz)) => time.with_timezone(&tz).format("%H:%M").to_string(),
+        _ => "--".to_string(),
+    }
 }
 
-pub async fn run(config: &CliConfig) -> Result<()> {
+/// Dawn, sunrise, sunset, dusk and day length cells for a location
+fn solar_cells(time: &LocationTimeResponse) -> Vec<String> {
+    let Some(solar) = &time.solar else {
+        return vec!["-".to_string(); 5];
+    };
+    let timezone = &time.location.timezone;
+
+    vec![
+        local_hm(solar.civil_dawn, timezone),
+        local_hm(solar.sunrise, timezone),
+        local_hm(solar.sunset, timezone),
+        local_hm(solar.civil_dusk, timezone),
+        format!(
+            "{}h {:02}m",
+            solar.day_length_seconds / 3600,
+            (solar.day_length_seconds % 3600) / 60
+        ),
+    ]
+}
+
+pub async fn run(config: &CliConfig, group: Option<&str>) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -39,7 +79,7 @@ pub async fn run(config: &CliConfig) -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Run the app
-    let res = run_app(&mut terminal, config).await;
+    let res = run_app(&mut terminal, config, group).await;
 
     // Restore terminal
     disable_raw_mode()?;
@@ -60,20 +100,23 @@ pub async fn run(config: &CliConfig) -> Result<()> {
 async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     config: &CliConfig,
+    group: Option<&str>,
 ) -> Result<()> {
-    loop {
-        // Check for keyboard events (non-blocking)
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.code == KeyCode::Char('q') {
-                    break;
-                }
-            }
-        }
+    let mut groups = GroupSelector::load(config, group).await;
 
+    loop {
         // Fetch location times from API
         let client = reqwest::Client::new();
-        let url = format!("{}/api/v1/locations/times", config.server_url);
+        let mut params = vec![groups.query()];
+        if let Some(format) = &config.time_format {
+            params.push(format!("format={}", urlencoding::encode(format)));
+        }
+        params.retain(|param| !param.is_empty());
+        let url = format!(
+            "{}/api/v1/locations/times?{}",
+            config.server_url,
+            params.join("&")
+        );
 
         let times = match client
             .get(&url)
@@ -112,7 +155,7 @@ async fn run_app(
                 .split(size);
 
             // Title
-            let title = Paragraph::new("World Clocks")
+            let title = Paragraph::new(format!("World Clocks - {}", groups.label()))
                 .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::ALL));
@@ -126,28 +169,43 @@ async fn run_app(
                     .block(Block::default().borders(Borders::ALL));
                 f.render_widget(no_locations, chunks[1]);
             } else {
-                let header = Row::new(vec!["Location", "Timezone", "Current Time"])
+                let header = Row::new(vec![
+                    "Location",
+                    "Timezone",
+                    "Current Time",
+                    "Dawn",
+                    "Sunrise",
+                    "Sunset",
+                    "Dusk",
+                    "Daylight",
+                ])
                     .style(Style::default().add_modifier(Modifier::BOLD))
                     .bottom_margin(1);
 
                 let rows: Vec<Row> = times
                     .iter()
                     .map(|time| {
-                        Row::new(vec![
+                        let mut cells = vec![
                             time.location.name.clone(),
                             time.location.timezone.clone(),
                             time.formatted_time.clone(),
-                        ])
-                        .style(Style::default().fg(Color::Green))
+                        ];
+                        cells.extend(solar_cells(time));
+                        Row::new(cells).style(Style::default().fg(Color::Green))
                     })
                     .collect();
 
                 let table = Table::new(
                     rows,
                     [
-                        Constraint::Percentage(30),
-                        Constraint::Percentage(30),
-                        Constraint::Percentage(40),
+                        Constraint::Percentage(16),
+                        Constraint::Percentage(18),
+                        Constraint::Percentage(26),
+                        Constraint::Length(6),
+                        Constraint::Length(7),
+                        Constraint::Length(6),
+                        Constraint::Length(6),
+                        Constraint::Length(8),
                     ],
                 )
                 .header(header)
@@ -157,14 +215,26 @@ async fn run_app(
             }
 
             // Help text at bottom
-            let help = Paragraph::new("Press 'q' to quit")
+            let help = if groups.has_groups() {
+                "Tab/Shift+Tab switch group | 'a' all locations | 'q' quit"
+            } else {
+                "Press 'q' to quit"
+            };
+            let help = Paragraph::new(help)
                 .style(Style::default().fg(Color::DarkGray))
                 .alignment(Alignment::Center);
             f.render_widget(help, chunks[2]);
         })?;
 
-        // Sleep a bit before next update
-        sleep(Duration::from_secs(1)).await;
+        // Wait for a key press, refreshing the clocks every second
+        if event::poll(Duration::from_secs(1))? {
+            if let Event::Key(key) = event::read()? {
+                if key.code == KeyCode::Char('q') {
+                    break;
+                }
+                groups.handle_key(key.code);
+            }
+        }
     }
 
     Ok(())
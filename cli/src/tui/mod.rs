@@ -2,3 +2,6 @@ pub mod timer_watch;
 pub mod watch_all;
 pub mod location_watch_all;
 pub mod weather_watch_all;
+pub mod weather_history;
+pub mod location_plan;
+pub mod groups;
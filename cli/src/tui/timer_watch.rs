@@ -28,7 +28,6 @@ use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
 struct TimerResponse {
-    id: Uuid,
     name: String,
     duration_seconds: i32,
     status: String,
@@ -177,7 +176,7 @@ async fn run_app(
                     let gauge = Gauge::default()
                         .block(Block::default().borders(Borders::ALL).title("Progress"))
                         .gauge_style(Style::default().fg(Color::Green))
-                        .ratio(progress.max(0.0).min(1.0))
+                        .ratio(progress.clamp(0.0, 1.0))
                         .label(format!(
                             "{} / {} seconds",
                             timer.duration_seconds - remaining,
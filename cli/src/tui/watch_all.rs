@@ -9,18 +9,15 @@ use ratatui::{
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Row, Table},
     Terminal,
 };
 use serde::Deserialize;
 use std::io;
 use tokio::time::{sleep, Duration};
-use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
 struct TimerResponse {
-    id: Uuid,
     name: String,
     duration_seconds: i32,
     status: String,
@@ -135,7 +132,7 @@ async fn run_app(
                     .map(|timer| {
                         let remaining = timer
                             .remaining_seconds
-                            .map(|s| format_time(s))
+                            .map(format_time)
                             .unwrap_or_else(|| "--:--".to_string());
 
                         Row::new(vec![
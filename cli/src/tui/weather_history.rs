@@ -0,0 +1,312 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local, Utc};
+use crossterm::{
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use lib::config::CliConfig;
+use lib::weather::units::UnitSystem;
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::Span,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    Terminal,
+};
+use serde::Deserialize;
+use std::io;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+struct WeatherHistoryResponse {
+    location_name: String,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    units: UnitSystem,
+    points: Vec<WeatherHistoryPoint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WeatherHistoryPoint {
+    period_start: DateTime<Utc>,
+    temperature_min: f32,
+    temperature_avg: f32,
+    temperature_max: f32,
+}
+
+/// Which aggregation and range is shown
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum View {
+    /// Hourly points over the last `days` days
+    Hourly { days: i64 },
+    /// Daily points over the last `days` days
+    Daily { days: i64 },
+}
+
+impl View {
+    fn path(&self, location_id: Uuid, units: UnitSystem) -> String {
+        let (interval, days) = match self {
+            View::Hourly { days } => ("hourly", days),
+            View::Daily { days } => ("daily", days),
+        };
+        let from = Utc::now() - Duration::days(*days);
+        format!(
+            "/api/v1/weather/locations/{}/history?interval={}&from={}&units={}",
+            location_id,
+            interval,
+            urlencoding::encode(&from.to_rfc3339()),
+            units
+        )
+    }
+
+    fn label(&self) -> String {
+        match self {
+            View::Hourly { days } => format!("hourly, last {} days", days),
+            View::Daily { days } => format!("daily, last {} days", days),
+        }
+    }
+}
+
+pub async fn run(config: &CliConfig, location_id: Uuid, days: i64, daily: bool) -> Result<()> {
+    // Setup terminal
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    // Run the app
+    let view = if daily {
+        View::Daily { days }
+    } else {
+        View::Hourly { days }
+    };
+    let res = run_app(&mut terminal, config, location_id, view).await;
+
+    // Restore terminal
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    if let Err(err) = res {
+        println!("Error: {:?}", err);
+    }
+
+    Ok(())
+}
+
+async fn fetch_history(
+    config: &CliConfig,
+    location_id: Uuid,
+    view: View,
+) -> Result<WeatherHistoryResponse, String> {
+    let client = reqwest::Client::new();
+    let path = view.path(location_id, config.units.unwrap_or_default());
+    let url = format!("{}{}", config.server_url, path);
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", config.api_token))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("Server returned {}", response.status()));
+    }
+
+    response.json().await.map_err(|e| e.to_string())
+}
+
+async fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    config: &CliConfig,
+    location_id: Uuid,
+    mut view: View,
+) -> Result<()> {
+    let mut history = fetch_history(config, location_id, view).await;
+
+    loop {
+        terminal.draw(|f| {
+            let size = f.area();
+
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(2)
+                .constraints(
+                    [
+                        Constraint::Length(3),
+                        Constraint::Min(1),
+                        Constraint::Length(1),
+                    ]
+                    .as_ref(),
+                )
+                .split(size);
+
+            // Title
+            let title = match &history {
+                Ok(history) => format!(
+                    "Weather History - {} ({})",
+                    history.location_name,
+                    view.label()
+                ),
+                Err(_) => format!("Weather History ({})", view.label()),
+            };
+            let title = Paragraph::new(title)
+                .style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL));
+            f.render_widget(title, chunks[0]);
+
+            match &history {
+                Err(err) => {
+                    let error = Paragraph::new(format!("Failed to load history: {}", err))
+                        .style(Style::default().fg(Color::Red))
+                        .alignment(Alignment::Center)
+                        .block(Block::default().borders(Borders::ALL));
+                    f.render_widget(error, chunks[1]);
+                }
+                Ok(history) if history.points.is_empty() => {
+                    let empty = Paragraph::new(
+                        "No observations recorded in this range yet.\n\
+                         The server records weather for locations with coordinates periodically.",
+                    )
+                    .style(Style::default().fg(Color::DarkGray))
+                    .alignment(Alignment::Center)
+                    .block(Block::default().borders(Borders::ALL));
+                    f.render_widget(empty, chunks[1]);
+                }
+                Ok(history) => {
+                    let series = Series::new(history);
+                    f.render_widget(series.chart(history), chunks[1]);
+                }
+            }
+
+            // Help text at bottom
+            let help =
+                Paragraph::new("'h' hourly | 'd' daily | +/- range | 'r' refresh | 'q' quit")
+                    .style(Style::default().fg(Color::DarkGray))
+                    .alignment(Alignment::Center);
+            f.render_widget(help, chunks[2]);
+        })?;
+
+        if let Event::Key(key) = event::read()? {
+            let next = match (key.code, view) {
+                (KeyCode::Char('q') | KeyCode::Esc, _) => break,
+                (KeyCode::Char('h'), View::Daily { days }) => View::Hourly { days },
+                (KeyCode::Char('d'), View::Hourly { days }) => View::Daily { days },
+                (KeyCode::Char('+'), View::Hourly { days }) => View::Hourly { days: days * 2 },
+                (KeyCode::Char('+'), View::Daily { days }) => View::Daily { days: days * 2 },
+                (KeyCode::Char('-'), View::Hourly { days }) => View::Hourly {
+                    days: (days / 2).max(1),
+                },
+                (KeyCode::Char('-'), View::Daily { days }) => View::Daily {
+                    days: (days / 2).max(1),
+                },
+                (KeyCode::Char('r'), view) => view,
+                _ => continue,
+            };
+
+            view = next;
+            history = fetch_history(config, location_id, view).await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Chart data: x is hours since the start of the range, y is the temperature
+struct Series {
+    min: Vec<(f64, f64)>,
+    avg: Vec<(f64, f64)>,
+    max: Vec<(f64, f64)>,
+}
+
+impl Series {
+    fn new(history: &WeatherHistoryResponse) -> Self {
+        let x = |at: DateTime<Utc>| (at - history.from).num_minutes() as f64 / 60.0;
+        let points = |value: fn(&WeatherHistoryPoint) -> f32| {
+            history
+                .points
+                .iter()
+                .map(|point| (x(point.period_start), value(point) as f64))
+                .collect()
+        };
+
+        Self {
+            min: points(|p| p.temperature_min),
+            avg: points(|p| p.temperature_avg),
+            max: points(|p| p.temperature_max),
+        }
+    }
+
+    fn chart<'a>(&'a self, history: &WeatherHistoryResponse) -> Chart<'a> {
+        let low = self
+            .min
+            .iter()
+            .map(|(_, y)| *y)
+            .fold(f64::INFINITY, f64::min);
+        let high = self
+            .max
+            .iter()
+            .map(|(_, y)| *y)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let (low, high) = ((low - 1.0).floor(), (high + 1.0).ceil());
+        let span_hours = (history.to - history.from).num_minutes() as f64 / 60.0;
+
+        let time_label = |at: DateTime<Utc>| {
+            Span::raw(at.with_timezone(&Local).format("%d %b %H:%M").to_string())
+        };
+        let middle = history.from + (history.to - history.from) / 2;
+
+        let dataset = |name: &'static str, color: Color, data: &'a [(f64, f64)]| {
+            Dataset::default()
+                .name(name)
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(color))
+                .data(data)
+        };
+
+        Chart::new(vec![
+            dataset("max", Color::Red, &self.max),
+            dataset("avg", Color::Green, &self.avg),
+            dataset("min", Color::Blue, &self.min),
+        ])
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "Temperature ({})",
+            history.units.labels().temperature
+        )))
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds([0.0, span_hours])
+                .labels(vec![
+                    time_label(history.from),
+                    time_label(middle),
+                    time_label(history.to),
+                ]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds([low, high])
+                .labels(vec![
+                    Span::raw(format!("{:.0}", low)),
+                    Span::raw(format!("{:.0}", (low + high) / 2.0)),
+                    Span::raw(format!("{:.0}", high)),
+                ]),
+        )
+    }
+}
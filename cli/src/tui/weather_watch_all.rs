@@ -5,30 +5,84 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use lib::config::CliConfig;
+use lib::weather::air::{AqiCategory, UvRisk};
+use lib::weather::units::{compass_direction, UnitSystem};
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Paragraph, Row, Table},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
     Terminal,
 };
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::io;
-use tokio::time::{sleep, Duration};
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+use super::groups::GroupSelector;
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+/// Forecasts and air quality change slowly, so they are refreshed less often than current
+/// conditions
+const FORECAST_REFRESH_INTERVAL: Duration = Duration::from_secs(600);
+/// Hours of forecast shown in the sparkline column
+const SPARKLINE_HOURS: usize = 24;
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
 
 #[derive(Debug, Deserialize)]
 struct WeatherResponse {
+    location_id: Uuid,
     location_name: String,
-    temperature_celsius: f32,
-    temperature_fahrenheit: f32,
-    feels_like_celsius: f32,
+    units: UnitSystem,
+    temperature: f32,
+    feels_like: f32,
     humidity: u8,
     description: String,
-    wind_speed_ms: f32,
-    wind_speed_mph: f32,
+    wind_speed: f32,
+    wind_direction_degrees: Option<u16>,
+    /// Served from the server's cache past its freshness window
+    stale: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct HourlyForecastResponse {
+    hours: Vec<HourlyForecastEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HourlyForecastEntry {
+    temperature_celsius: f32,
 }
 
-pub async fn run(config: &CliConfig) -> Result<()> {
+#[derive(Debug, Deserialize)]
+struct AirQualityResponse {
+    category: AqiCategory,
+    uv_index: Option<f32>,
+    uv_risk: Option<UvRisk>,
+}
+
+/// Render values as a row of block characters scaled between their minimum and maximum
+pub fn sparkline(values: &[f32]) -> String {
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let span = max - min;
+
+    values
+        .iter()
+        .map(|value| {
+            let level = if span > 0.0 {
+                ((value - min) / span * (SPARK_LEVELS.len() - 1) as f32).round() as usize
+            } else {
+                SPARK_LEVELS.len() / 2
+            };
+            SPARK_LEVELS[level.min(SPARK_LEVELS.len() - 1)]
+        })
+        .collect()
+}
+
+pub async fn run(config: &CliConfig, group: Option<&str>) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -37,7 +91,7 @@ pub async fn run(config: &CliConfig) -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Run the app
-    let res = run_app(&mut terminal, config).await;
+    let res = run_app(&mut terminal, config, group).await;
 
     // Restore terminal
     disable_raw_mode()?;
@@ -58,39 +112,34 @@ pub async fn run(config: &CliConfig) -> Result<()> {
 async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     config: &CliConfig,
+    group: Option<&str>,
 ) -> Result<()> {
+    let mut groups = GroupSelector::load(config, group).await;
+    let mut weather_list = Vec::new();
+    let mut last_fetch: Option<Instant> = None;
+    let mut forecasts: HashMap<Uuid, Vec<f32>> = HashMap::new();
+    let mut air_quality: HashMap<Uuid, AirQualityResponse> = HashMap::new();
+    let mut last_forecast_fetch: Option<Instant> = None;
+
     loop {
-        // Check for keyboard events (non-blocking)
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.code == KeyCode::Char('q') {
-                    break;
-                }
-            }
+        // Refresh every 30 seconds (to avoid API rate limits) or when the group changes
+        if last_fetch.is_none_or(|fetched| fetched.elapsed() >= REFRESH_INTERVAL) {
+            weather_list = fetch_weather(config, &groups).await;
+            last_fetch = Some(Instant::now());
         }
 
-        // Fetch weather from API
-        let client = reqwest::Client::new();
-        let url = format!("{}/api/v1/weather/locations", config.server_url);
-
-        let weather_list = match client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", config.api_token))
-            .send()
-            .await
+        let missing_forecast = weather_list
+            .iter()
+            .any(|weather| !forecasts.contains_key(&weather.location_id));
+        if missing_forecast
+            || last_forecast_fetch.is_none_or(|f| f.elapsed() >= FORECAST_REFRESH_INTERVAL)
         {
-            Ok(response) => {
-                if response.status().is_success() {
-                    response
-                        .json::<Vec<WeatherResponse>>()
-                        .await
-                        .unwrap_or_default()
-                } else {
-                    vec![]
-                }
-            }
-            Err(_) => vec![],
-        };
+            (forecasts, air_quality) = tokio::join!(
+                fetch_forecasts(config, &weather_list),
+                fetch_air_quality(config, &weather_list)
+            );
+            last_forecast_fetch = Some(Instant::now());
+        }
 
         // Draw the UI
         terminal.draw(|f| {
@@ -110,7 +159,7 @@ async fn run_app(
                 .split(size);
 
             // Title
-            let title = Paragraph::new("Weather Dashboard")
+            let title = Paragraph::new(format!("Weather Dashboard - {}", groups.label()))
                 .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::ALL));
@@ -131,6 +180,9 @@ async fn run_app(
                     "Conditions",
                     "Humidity",
                     "Wind",
+                    "Air",
+                    "UV",
+                    "Next 24h",
                 ])
                 .style(Style::default().add_modifier(Modifier::BOLD))
                 .bottom_margin(1);
@@ -138,29 +190,61 @@ async fn run_app(
                 let rows: Vec<Row> = weather_list
                     .iter()
                     .map(|weather| {
+                        let labels = weather.units.labels();
+                        let wind_from = weather
+                            .wind_direction_degrees
+                            .map(compass_direction)
+                            .unwrap_or_default();
+                        let air = air_quality.get(&weather.location_id);
+                        let air_cell = match air {
+                            Some(air) => Cell::from(air.category.to_string())
+                                .style(Style::default().fg(aqi_color(air.category))),
+                            None => Cell::from("-"),
+                        };
+                        let uv_cell = match air.and_then(|air| air.uv_index.zip(air.uv_risk)) {
+                            Some((index, risk)) => Cell::from(format!("{:.1} {}", index, risk))
+                                .style(Style::default().fg(uv_color(risk))),
+                            None => Cell::from("-"),
+                        };
                         Row::new(vec![
-                            weather.location_name.clone(),
-                            format!("{:.1}C/{:.1}F",
-                                weather.temperature_celsius,
-                                weather.temperature_fahrenheit),
-                            format!("{:.1}C", weather.feels_like_celsius),
-                            weather.description.clone(),
-                            format!("{}%", weather.humidity),
-                            format!("{:.1}mph", weather.wind_speed_mph),
+                            Cell::from(weather.location_name.clone()),
+                            Cell::from(format!("{:.1}{}", weather.temperature, labels.temperature)),
+                            Cell::from(format!("{:.1}{}", weather.feels_like, labels.temperature)),
+                            Cell::from(weather.description.clone()),
+                            Cell::from(format!("{}%", weather.humidity)),
+                            Cell::from(format!(
+                                "{:.1}{} {}",
+                                weather.wind_speed, labels.speed, wind_from
+                            )),
+                            air_cell,
+                            uv_cell,
+                            Cell::from(
+                                forecasts
+                                    .get(&weather.location_id)
+                                    .map(|temperatures| sparkline(temperatures))
+                                    .unwrap_or_default(),
+                            ),
                         ])
-                        .style(Style::default().fg(Color::Green))
+                        .style(Style::default().fg(if weather.stale {
+                            Color::Yellow
+                        } else {
+                            Color::Green
+                        }))
                     })
                     .collect();
 
                 let table = Table::new(
                     rows,
                     [
-                        Constraint::Percentage(20),
-                        Constraint::Percentage(15),
                         Constraint::Percentage(12),
-                        Constraint::Percentage(25),
-                        Constraint::Percentage(13),
+                        Constraint::Percentage(8),
+                        Constraint::Percentage(8),
                         Constraint::Percentage(15),
+                        Constraint::Percentage(7),
+                        Constraint::Percentage(11),
+                        Constraint::Percentage(10),
+                        Constraint::Percentage(11),
+                        Constraint::Percentage(18),
                     ],
                 )
                 .header(header)
@@ -170,15 +254,169 @@ async fn run_app(
             }
 
             // Help text at bottom
-            let help = Paragraph::new("Press 'q' to quit | Updates every 30 seconds")
+            let help = if groups.has_groups() {
+                "Tab/Shift+Tab switch group | 'a' all locations | 'q' quit | Updates every 30 seconds"
+            } else {
+                "Press 'q' to quit | Updates every 30 seconds"
+            };
+            let help = Paragraph::new(help)
                 .style(Style::default().fg(Color::DarkGray))
                 .alignment(Alignment::Center);
             f.render_widget(help, chunks[2]);
         })?;
 
-        // Sleep before next update (30 seconds to avoid API rate limits)
-        sleep(Duration::from_secs(30)).await;
+        // Wait for a key press, redrawing every second
+        if event::poll(Duration::from_secs(1))? {
+            if let Event::Key(key) = event::read()? {
+                if key.code == KeyCode::Char('q') {
+                    break;
+                }
+                if groups.handle_key(key.code) {
+                    last_fetch = None;
+                }
+            }
+        }
     }
 
     Ok(())
 }
+
+async fn fetch_weather(config: &CliConfig, groups: &GroupSelector) -> Vec<WeatherResponse> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "{}/api/v1/weather/locations?units={}&{}",
+        config.server_url,
+        config.units.unwrap_or_default(),
+        groups.query()
+    );
+
+    match client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", config.api_token))
+        .send()
+        .await
+    {
+        Ok(response) => {
+            if response.status().is_success() {
+                response
+                    .json::<Vec<WeatherResponse>>()
+                    .await
+                    .unwrap_or_default()
+            } else {
+                vec![]
+            }
+        }
+        Err(_) => vec![],
+    }
+}
+
+/// Fetch the next 24 hours of forecast temperatures for each location (empty if unavailable).
+///
+/// Locations are fetched concurrently; the server bounds the upstream requests.
+async fn fetch_forecasts(
+    config: &CliConfig,
+    weather_list: &[WeatherResponse],
+) -> HashMap<Uuid, Vec<f32>> {
+    let client = reqwest::Client::new();
+    let mut fetches = JoinSet::new();
+
+    for weather in weather_list {
+        let request = client
+            .get(format!(
+                "{}/api/v1/weather/locations/{}/forecast/hourly",
+                config.server_url, weather.location_id
+            ))
+            .header("Authorization", format!("Bearer {}", config.api_token));
+        let location_id = weather.location_id;
+
+        fetches.spawn(async move {
+            let forecast = match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    response.json::<HourlyForecastResponse>().await.ok()
+                }
+                _ => None,
+            };
+            (location_id, forecast)
+        });
+    }
+
+    let mut forecasts = HashMap::new();
+    while let Some(fetched) = fetches.join_next().await {
+        let Ok((location_id, forecast)) = fetched else {
+            continue;
+        };
+        let temperatures = forecast
+            .map(|forecast| {
+                forecast
+                    .hours
+                    .iter()
+                    .take(SPARKLINE_HOURS)
+                    .map(|hour| hour.temperature_celsius)
+                    .collect()
+            })
+            .unwrap_or_default();
+        forecasts.insert(location_id, temperatures);
+    }
+
+    forecasts
+}
+
+/// Current air quality and UV index for each location, fetched concurrently (locations that
+/// fail are left out)
+async fn fetch_air_quality(
+    config: &CliConfig,
+    weather_list: &[WeatherResponse],
+) -> HashMap<Uuid, AirQualityResponse> {
+    let client = reqwest::Client::new();
+    let mut fetches = JoinSet::new();
+
+    for weather in weather_list {
+        let request = client
+            .get(format!(
+                "{}/api/v1/weather/locations/{}/air-quality",
+                config.server_url, weather.location_id
+            ))
+            .header("Authorization", format!("Bearer {}", config.api_token));
+        let location_id = weather.location_id;
+
+        fetches.spawn(async move {
+            let air = match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    response.json::<AirQualityResponse>().await.ok()
+                }
+                _ => None,
+            };
+            (location_id, air)
+        });
+    }
+
+    let mut air_quality = HashMap::new();
+    while let Some(fetched) = fetches.join_next().await {
+        if let Ok((location_id, Some(air))) = fetched {
+            air_quality.insert(location_id, air);
+        }
+    }
+
+    air_quality
+}
+
+fn aqi_color(category: AqiCategory) -> Color {
+    match category {
+        AqiCategory::Good => Color::Green,
+        AqiCategory::Fair => Color::LightGreen,
+        AqiCategory::Moderate => Color::Yellow,
+        AqiCategory::Poor => Color::LightRed,
+        AqiCategory::VeryPoor => Color::Red,
+        AqiCategory::ExtremelyPoor => Color::Magenta,
+    }
+}
+
+fn uv_color(risk: UvRisk) -> Color {
+    match risk {
+        UvRisk::Low => Color::Green,
+        UvRisk::Moderate => Color::Yellow,
+        UvRisk::High => Color::LightRed,
+        UvRisk::VeryHigh => Color::Red,
+        UvRisk::Extreme => Color::Magenta,
+    }
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use uuid::Uuid;
+
+use crate::weather::units::UnitSystem;
 
 pub mod cli;
 pub mod server;
@@ -8,6 +11,15 @@ pub mod server;
 pub struct CliConfig {
     pub server_url: String,
     pub api_token: String,
+    /// Local override for the current location's ID (takes precedence over the server default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_location_id: Option<Uuid>,
+    /// How times are shown: `24h`, `12h`, `rfc3339` or a strftime pattern
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_format: Option<String>,
+    /// Unit system for weather: `metric`, `imperial` or `si`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub units: Option<UnitSystem>,
 }
 
 impl Default for CliConfig {
@@ -15,6 +27,9 @@ impl Default for CliConfig {
         Self {
             server_url: "http://localhost:3000".to_string(),
             api_token: String::new(),
+            default_location_id: None,
+            time_format: None,
+            units: None,
         }
     }
 }
@@ -25,6 +40,40 @@ pub struct ServerConfig {
     pub database_url: String,
     pub api_tokens: Vec<String>,
     pub weather_api_key: Option<String>,
+    /// Where current weather comes from
+    pub weather_provider: WeatherProviderKind,
+    /// Base URL of the OpenWeatherMap API (overridable for testing)
+    pub openweather_base_url: String,
+    /// Base URL of the Open-Meteo forecast API (overridable for testing)
+    pub openmeteo_base_url: String,
+    /// Base URL of the Open-Meteo air quality API (overridable for testing)
+    pub openmeteo_air_quality_base_url: String,
+    /// Seconds current weather for a location is served from the cache without refreshing
+    pub weather_cache_ttl_secs: i64,
+    /// Seconds past the TTL that cached weather is still served while it is refreshed
+    pub weather_cache_max_stale_secs: i64,
+    /// Most weather requests sent upstream at the same time
+    pub weather_fetch_concurrency: usize,
+    /// Minutes between weather snapshots for the history (0 disables recording)
+    pub weather_snapshot_interval_mins: u64,
+    /// Days weather observations are kept
+    pub weather_retention_days: i64,
+    /// Minutes between weather rule evaluations (0 disables alerting)
+    pub weather_alert_interval_mins: u64,
+    /// URL that firing and resolved weather alerts are POSTed to
+    pub weather_alert_webhook_url: Option<String>,
+    /// Which geocoder resolves city names
+    pub geocoder: GeocoderKind,
+    /// Hours upstream geocoding results are cached
+    pub geocoding_cache_ttl_hours: i64,
+    /// Hours a dropped database stays in the trash before it is purged
+    pub database_trash_grace_hours: i64,
+    /// Database names that can never be dropped (the control database is always protected)
+    pub protected_databases: Vec<String>,
+    /// Name patterns (`*` and `?` wildcards) for databases that can never be dropped
+    pub protected_database_patterns: Vec<String>,
+    /// Base64-encoded 256-bit key used to encrypt stored secrets
+    pub secret_key: Option<String>,
 }
 
 impl Default for ServerConfig {
@@ -34,10 +83,98 @@ impl Default for ServerConfig {
             database_url: String::new(),
             api_tokens: vec![],
             weather_api_key: None,
+            weather_provider: WeatherProviderKind::default(),
+            openweather_base_url: DEFAULT_OPENWEATHER_BASE_URL.to_string(),
+            openmeteo_base_url: DEFAULT_OPENMETEO_BASE_URL.to_string(),
+            openmeteo_air_quality_base_url: DEFAULT_OPENMETEO_AIR_QUALITY_BASE_URL.to_string(),
+            weather_cache_ttl_secs: 600,
+            weather_cache_max_stale_secs: 3600,
+            weather_fetch_concurrency: 4,
+            weather_snapshot_interval_mins: 30,
+            weather_retention_days: 90,
+            weather_alert_interval_mins: 5,
+            weather_alert_webhook_url: None,
+            geocoder: GeocoderKind::default(),
+            geocoding_cache_ttl_hours: 168,
+            database_trash_grace_hours: 72,
+            protected_databases: default_protected_databases(),
+            protected_database_patterns: vec![],
+            secret_key: None,
+        }
+    }
+}
+
+pub const DEFAULT_OPENWEATHER_BASE_URL: &str = "https://api.openweathermap.org";
+pub const DEFAULT_OPENMETEO_BASE_URL: &str = "https://api.open-meteo.com";
+pub const DEFAULT_OPENMETEO_AIR_QUALITY_BASE_URL: &str = "https://air-quality-api.open-meteo.com";
+
+/// Weather data provider
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeatherProviderKind {
+    /// OpenWeatherMap when an API key is configured, otherwise Open-Meteo
+    #[default]
+    Auto,
+    /// OpenWeatherMap (requires an API key)
+    OpenWeather,
+    /// Open-Meteo, no API key needed
+    OpenMeteo,
+}
+
+impl std::str::FromStr for WeatherProviderKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "auto" => Ok(WeatherProviderKind::Auto),
+            "openweather" => Ok(WeatherProviderKind::OpenWeather),
+            "openmeteo" | "open-meteo" => Ok(WeatherProviderKind::OpenMeteo),
+            other => anyhow::bail!(
+                "Unknown weather provider '{}' (expected auto, openweather or openmeteo)",
+                other
+            ),
         }
     }
 }
 
+/// Geocoder used to look up city names
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GeocoderKind {
+    /// OpenWeatherMap when an API key is configured, otherwise the bundled dataset
+    #[default]
+    Auto,
+    /// OpenWeatherMap geocoding API (requires an API key)
+    OpenWeather,
+    /// Bundled city dataset, no network access needed
+    Offline,
+}
+
+impl std::str::FromStr for GeocoderKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "auto" => Ok(GeocoderKind::Auto),
+            "openweather" => Ok(GeocoderKind::OpenWeather),
+            "offline" => Ok(GeocoderKind::Offline),
+            other => anyhow::bail!(
+                "Unknown geocoder '{}' (expected auto, openweather or offline)",
+                other
+            ),
+        }
+    }
+}
+
+/// Postgres system databases that are protected unless overridden
+pub fn default_protected_databases() -> Vec<String> {
+    vec![
+        "postgres".to_string(),
+        "template0".to_string(),
+        "template1".to_string(),
+    ]
+}
+
 /// Get the path to the CLI config file
 pub fn cli_config_path() -> anyhow::Result<PathBuf> {
     let config_dir = dirs::config_dir()
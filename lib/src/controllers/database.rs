@@ -1,29 +1,54 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
 };
+use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::controllers::timer::{AppError, AppState};
-use crate::models::database::CreateDatabaseRequest;
+use crate::models::database::{
+    CreateDatabaseRequest, ExecSqlRequest, MigrateRequest, SetProtectionRequest,
+};
 use crate::services::database::DatabaseService;
 
+#[derive(Debug, Deserialize)]
+pub struct ListDatabasesQuery {
+    #[serde(default)]
+    deleted: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DropDatabaseQuery {
+    #[serde(default)]
+    hard: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DatabaseExistsQuery {
+    server: Option<String>,
+}
+
 /// Create a new database
 pub async fn create_database(
     State(state): State<Arc<AppState>>,
     Json(req): Json<CreateDatabaseRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let database = DatabaseService::create(&state.db, req).await?;
+    let database = DatabaseService::create(&state.db, &state.clusters, req).await?;
     Ok((StatusCode::CREATED, Json(database)))
 }
 
-/// List all managed databases
+/// List all managed databases (or the trash, with `?deleted=true`)
 pub async fn list_databases(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<ListDatabasesQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let databases = DatabaseService::list(&state.db).await?;
+    let databases = if query.deleted {
+        DatabaseService::list_trash(&state.db).await?
+    } else {
+        DatabaseService::list(&state.db).await?
+    };
     Ok(Json(databases))
 }
 
@@ -38,20 +63,85 @@ pub async fn get_database(
     Ok(Json(database))
 }
 
-/// Drop a database
+/// Drop a database (moves it to the trash unless `?hard=true`)
 pub async fn drop_database(
     State(state): State<Arc<AppState>>,
     Path(db_name): Path<String>,
+    Query(query): Query<DropDatabaseQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let database = if query.hard {
+        DatabaseService::drop(&state.db, &state.clusters, &state.config, &db_name).await?
+    } else {
+        DatabaseService::trash(&state.db, &state.clusters, &state.config, &db_name).await?
+    };
+    Ok(Json(database))
+}
+
+/// Set or clear drop protection on a database
+pub async fn set_database_protection(
+    State(state): State<Arc<AppState>>,
+    Path(db_name): Path<String>,
+    Json(req): Json<SetProtectionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let database = DatabaseService::set_protected(&state.db, &db_name, req.protected)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(database))
+}
+
+/// Restore a database from the trash
+pub async fn undelete_database(
+    State(state): State<Arc<AppState>>,
+    Path(db_name): Path<String>,
 ) -> Result<impl IntoResponse, AppError> {
-    let database = DatabaseService::drop(&state.db, &db_name).await?;
+    let grace_period = chrono::Duration::hours(state.config.database_trash_grace_hours);
+    let database =
+        DatabaseService::undelete(&state.db, &state.clusters, &db_name, grace_period).await?;
     Ok(Json(database))
 }
 
-/// Check if a database exists
+/// Check if a database exists (on the control cluster, or `?server=` a registered one)
 pub async fn check_database_exists(
     State(state): State<Arc<AppState>>,
     Path(db_name): Path<String>,
+    Query(query): Query<DatabaseExistsQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let exists = DatabaseService::exists(&state.db, &db_name).await?;
+    let target = state
+        .clusters
+        .target(&state.db, query.server.as_deref())
+        .await?;
+    let exists = DatabaseService::exists(&target.pool, &db_name).await?;
     Ok(Json(serde_json::json!({ "exists": exists })))
 }
+
+/// Get connection settings for a database
+pub async fn get_database_connection(
+    State(state): State<Arc<AppState>>,
+    Path(db_name): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let info = DatabaseService::connection_info(&state.db, &state.clusters, &state.config, &db_name)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(info))
+}
+
+/// Execute a SQL script against a database
+pub async fn exec_sql(
+    State(state): State<Arc<AppState>>,
+    Path(db_name): Path<String>,
+    Json(req): Json<ExecSqlRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let result = DatabaseService::exec(&state.db, &state.clusters, &db_name, &req.sql).await?;
+    Ok(Json(result))
+}
+
+/// Apply migrations to a database
+pub async fn migrate_database(
+    State(state): State<Arc<AppState>>,
+    Path(db_name): Path<String>,
+    Json(req): Json<MigrateRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let result =
+        DatabaseService::migrate(&state.db, &state.clusters, &db_name, req.migrations).await?;
+    Ok(Json(result))
+}
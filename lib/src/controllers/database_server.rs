@@ -0,0 +1,45 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+
+use crate::controllers::timer::{AppError, AppState};
+use crate::models::database::{CreateDatabaseServerRequest, DatabaseServerResponse};
+use crate::services::database_server::DatabaseServerService;
+
+/// Register a database server
+pub async fn create_database_server(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<CreateDatabaseServerRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let server = DatabaseServerService::create(&state.db, &state.config, req).await?;
+    let response = DatabaseServerService::to_response(&state.config, server)?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// List registered database servers
+pub async fn list_database_servers(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let servers = DatabaseServerService::list(&state.db).await?;
+    let responses = servers
+        .into_iter()
+        .map(|server| DatabaseServerService::to_response(&state.config, server))
+        .collect::<anyhow::Result<Vec<DatabaseServerResponse>>>()?;
+    Ok(Json(responses))
+}
+
+/// Remove a database server from the registry
+pub async fn delete_database_server(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let server = DatabaseServerService::delete(&state.db, &state.clusters, &name)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let response = DatabaseServerService::to_response(&state.config, server)?;
+    Ok(Json(response))
+}
@@ -1,30 +1,79 @@
 use axum::{
     extract::{Query, State},
+    http::{header, HeaderMap, HeaderValue},
     response::IntoResponse,
     Json,
 };
+use chrono::Utc;
 use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::controllers::timer::{AppError, AppState};
+use crate::location::Coordinates;
+use crate::models::geocoding::PurgeGeocodingCacheResponse;
 use crate::services::geocoding::GeocodingService;
 
+/// Candidates returned when the query does not ask for a number
+const DEFAULT_LIMIT: usize = 5;
+/// Most candidates a single lookup may return
+const MAX_LIMIT: usize = 20;
+
 #[derive(Debug, Deserialize)]
 pub struct GeocodingQuery {
     q: String,
+    /// Number of candidates to return (default 5)
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PurgeCacheQuery {
+    /// Only delete entries whose TTL has passed
+    #[serde(default)]
+    expired: bool,
 }
 
-/// Lookup city location data (lat, lon, timezone) from city name
+#[derive(Debug, Deserialize)]
+pub struct ReverseGeocodingQuery {
+    lat: f64,
+    lon: f64,
+}
+
+/// Lookup candidate locations (lat, lon, timezone) for a city name, best match first.
+///
+/// Results served from the cache carry an `Age` header with their age in seconds.
 pub async fn lookup_city(
     State(state): State<Arc<AppState>>,
     Query(query): Query<GeocodingQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let api_key = state
-        .config
-        .weather_api_key
-        .as_ref()
-        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Weather API key not configured")))?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let lookup =
+        GeocodingService::lookup_city(&state.db, &state.http, &query.q, limit, &state.config)
+            .await?;
+
+    let mut headers = HeaderMap::new();
+    if let Some(cached_at) = lookup.cached_at {
+        let age = (Utc::now() - cached_at).num_seconds().max(0);
+        headers.insert(header::AGE, HeaderValue::from(age));
+    }
+
+    Ok((headers, Json(lookup.results)))
+}
+
+/// Purge cached geocoding results
+pub async fn purge_geocoding_cache(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<PurgeCacheQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let purged = GeocodingService::purge_cache(&state.db, query.expired).await?;
+    Ok(Json(PurgeGeocodingCacheResponse { purged }))
+}
 
-    let result = GeocodingService::lookup_city(&query.q, api_key).await?;
+/// Find the place nearest to a point and the timezone there
+pub async fn reverse_geocode(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ReverseGeocodingQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let coordinates = Coordinates::new(query.lat, query.lon)?;
+    let result = GeocodingService::reverse(&state.http, &coordinates, &state.config).await?;
     Ok(Json(result))
 }
@@ -1,16 +1,144 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
 };
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::controllers::timer::AppError;
-use crate::controllers::timer::AppState;
-use crate::models::location::CreateLocationRequest;
+use crate::controllers::timer::{ApiToken, AppState};
+use crate::models::location::{
+    ConvertTimeRequest, CreateLocationRequest, OverlapRequest, SetDefaultLocationRequest,
+    TimeOptions, UpdateLocationRequest, WorkingHours,
+};
 use crate::services::location::LocationService;
+use crate::timezone::{parse_local_datetime, parse_time_of_day};
+
+#[derive(Debug, Deserialize)]
+pub struct OverlapQuery {
+    date: NaiveDate,
+    /// Comma-separated location IDs (all locations if omitted)
+    locations: Option<String>,
+    reference: Option<Uuid>,
+    /// Default working hours, `HH:MM`
+    start: Option<String>,
+    end: Option<String>,
+    /// Per-location working hours, `<id>=HH:MM-HH:MM,...`
+    hours: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListLocationsQuery {
+    /// Only list the locations in this group, in group order
+    group: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TimeQuery {
+    /// Only include the locations in this group, in group order
+    group: Option<String>,
+    /// `24h` (default), `12h`, `rfc3339` or a strftime pattern
+    format: Option<String>,
+    /// Location to compare offsets against instead of the caller's default location
+    relative_to: Option<Uuid>,
+}
+
+impl TimeQuery {
+    async fn to_options(
+        &self,
+        state: &AppState,
+        token: &ApiToken,
+    ) -> anyhow::Result<TimeOptions> {
+        let format = self.format.as_deref().unwrap_or_default().parse()?;
+
+        let reference = match self.relative_to {
+            Some(id) => LocationService::get_by_id(&state.db, id).await?,
+            None => LocationService::get_default(&state.db, &token.fingerprint()).await?,
+        };
+
+        Ok(TimeOptions { format, reference })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConvertTimeQuery {
+    /// Local time in the source zone, `YYYY-MM-DD HH:MM`
+    time: String,
+    /// Source location ID or IANA timezone name
+    from: String,
+    /// Comma-separated location IDs, or `all` (the default)
+    to: Option<String>,
+    /// `24h` (default), `12h`, `rfc3339` or a strftime pattern
+    format: Option<String>,
+}
+
+impl ConvertTimeQuery {
+    fn into_request(self) -> anyhow::Result<ConvertTimeRequest> {
+        let to = match self.to.as_deref() {
+            None | Some("all") => Vec::new(),
+            Some(ids) => ids
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(Uuid::parse_str)
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+
+        Ok(ConvertTimeRequest {
+            time: parse_local_datetime(&self.time)?,
+            from: self.from,
+            to,
+        })
+    }
+}
+
+impl OverlapQuery {
+    fn into_request(self) -> anyhow::Result<OverlapRequest> {
+        let location_ids = self
+            .locations
+            .iter()
+            .flat_map(|ids| ids.split(','))
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(Uuid::parse_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let default_hours = WorkingHours {
+            start: parse_time_of_day(self.start.as_deref().unwrap_or("09:00"))?,
+            end: parse_time_of_day(self.end.as_deref().unwrap_or("17:00"))?,
+        };
+
+        let mut hours = HashMap::new();
+        for entry in self.hours.iter().flat_map(|hours| hours.split(',')) {
+            let (id, window) = entry
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Invalid working hours '{}'", entry))?;
+            let (start, end) = window
+                .split_once('-')
+                .ok_or_else(|| anyhow::anyhow!("Invalid working hours '{}'", entry))?;
+            hours.insert(
+                Uuid::parse_str(id.trim())?,
+                WorkingHours {
+                    start: parse_time_of_day(start.trim())?,
+                    end: parse_time_of_day(end.trim())?,
+                },
+            );
+        }
+
+        Ok(OverlapRequest {
+            date: self.date,
+            location_ids,
+            reference: self.reference,
+            default_hours,
+            hours,
+        })
+    }
+}
 
 /// Create a new location
 pub async fn create_location(
@@ -32,14 +160,29 @@ pub async fn get_location(
     Ok(Json(location))
 }
 
-/// List all locations
+/// List all locations (or a group's locations, with `?group=`)
 pub async fn list_locations(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<ListLocationsQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let locations = LocationService::list(&state.db).await?;
+    let locations = LocationService::list_filtered(&state.db, query.group.as_deref())
+        .await?
+        .ok_or(AppError::NotFound)?;
     Ok(Json(locations))
 }
 
+/// Update a location
+pub async fn update_location(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateLocationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let location = LocationService::update(&state.db, id, req)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(location))
+}
+
 /// Delete a location
 pub async fn delete_location(
     State(state): State<Arc<AppState>>,
@@ -54,18 +197,77 @@ pub async fn delete_location(
 /// Get current time at a location
 pub async fn get_location_time(
     State(state): State<Arc<AppState>>,
+    Extension(token): Extension<ApiToken>,
     Path(id): Path<Uuid>,
+    Query(query): Query<TimeQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let response = LocationService::get_time(&state.db, id)
+    let options = query.to_options(&state, &token).await?;
+    let response = LocationService::get_time(&state.db, id, &options)
         .await?
         .ok_or(AppError::NotFound)?;
     Ok(Json(response))
 }
 
-/// Get times for all locations
+/// Get times for all locations (or a group's locations, with `?group=`)
 pub async fn list_location_times(
     State(state): State<Arc<AppState>>,
+    Extension(token): Extension<ApiToken>,
+    Query(query): Query<TimeQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let responses = LocationService::list_times(&state.db).await?;
+    let options = query.to_options(&state, &token).await?;
+    let responses = LocationService::list_times(&state.db, query.group.as_deref(), &options)
+        .await?
+        .ok_or(AppError::NotFound)?;
     Ok(Json(responses))
 }
+
+/// Get the default location for the calling API token
+pub async fn get_default_location(
+    State(state): State<Arc<AppState>>,
+    Extension(token): Extension<ApiToken>,
+) -> Result<impl IntoResponse, AppError> {
+    let location = LocationService::get_default(&state.db, &token.fingerprint())
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(location))
+}
+
+/// Set the default location for the calling API token
+pub async fn set_default_location(
+    State(state): State<Arc<AppState>>,
+    Extension(token): Extension<ApiToken>,
+    Json(req): Json<SetDefaultLocationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let location = LocationService::set_default(&state.db, &token.fingerprint(), req.location_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(location))
+}
+
+/// Clear the default location for the calling API token
+pub async fn clear_default_location(
+    State(state): State<Arc<AppState>>,
+    Extension(token): Extension<ApiToken>,
+) -> Result<impl IntoResponse, AppError> {
+    LocationService::clear_default(&state.db, &token.fingerprint()).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Find overlapping working hours across locations on a given day
+pub async fn get_location_overlap(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<OverlapQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = LocationService::overlap(&state.db, query.into_request()?).await?;
+    Ok(Json(response))
+}
+
+/// Convert a local time from one location to others
+pub async fn convert_location_time(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ConvertTimeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let format = query.format.as_deref().unwrap_or_default().parse()?;
+    let response = LocationService::convert(&state.db, query.into_request()?, &format).await?;
+    Ok(Json(response))
+}
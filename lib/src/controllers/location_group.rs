@@ -0,0 +1,91 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::controllers::timer::{AppError, AppState};
+use crate::models::location::{
+    AddGroupLocationRequest, CreateLocationGroupRequest, SetGroupLocationsRequest,
+};
+use crate::services::location_group::LocationGroupService;
+
+/// Create a location group
+pub async fn create_location_group(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<CreateLocationGroupRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let group = LocationGroupService::create(&state.db, req).await?;
+    let response = LocationGroupService::get_response(&state.db, &group.name)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// List all location groups with their locations
+pub async fn list_location_groups(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let groups = LocationGroupService::list(&state.db).await?;
+    Ok(Json(groups))
+}
+
+/// Get a location group by name
+pub async fn get_location_group(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let group = LocationGroupService::get_response(&state.db, &name)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(group))
+}
+
+/// Delete a location group
+pub async fn delete_location_group(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let group = LocationGroupService::delete(&state.db, &name)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(group))
+}
+
+/// Replace the locations in a group, in order
+pub async fn set_group_locations(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Json(req): Json<SetGroupLocationsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let group = LocationGroupService::set_locations(&state.db, &name, &req.location_ids)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(group))
+}
+
+/// Add a location to a group
+pub async fn add_group_location(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Json(req): Json<AddGroupLocationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let group = LocationGroupService::add_location(&state.db, &name, req.location_id, req.position)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(group))
+}
+
+/// Remove a location from a group
+pub async fn remove_group_location(
+    State(state): State<Arc<AppState>>,
+    Path((name, location_id)): Path<(String, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let group = LocationGroupService::remove_location(&state.db, &name, location_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(group))
+}
@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::controllers::timer::{AppError, AppState};
+use crate::models::team::{CreateTeamMemberRequest, UpdateTeamMemberRequest};
+use crate::services::team::TeamService;
+use crate::timezone::TimeFormat;
+
+#[derive(Debug, Deserialize)]
+pub struct TeamNowQuery {
+    /// `24h` (default), `12h`, `rfc3339` or a strftime pattern
+    format: Option<String>,
+}
+
+/// Add a team member
+pub async fn create_team_member(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<CreateTeamMemberRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let member = TeamService::create(&state.db, req).await?;
+    Ok((StatusCode::CREATED, Json(member)))
+}
+
+/// List all team members
+pub async fn list_team_members(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let members = TeamService::list(&state.db).await?;
+    Ok(Json(members))
+}
+
+/// Get a team member by ID
+pub async fn get_team_member(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let member = TeamService::get_by_id(&state.db, id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(member))
+}
+
+/// Partially update a team member
+pub async fn update_team_member(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateTeamMemberRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let member = TeamService::update(&state.db, id, req)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(member))
+}
+
+/// Remove a team member
+pub async fn delete_team_member(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let member = TeamService::delete(&state.db, id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(member))
+}
+
+/// Current local time of every team member and whether they are within working hours
+pub async fn get_team_now(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<TeamNowQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let format: TimeFormat = query.format.as_deref().unwrap_or_default().parse()?;
+    let members = TeamService::now(&state.db, &format).await?;
+    Ok(Json(members))
+}
@@ -10,12 +10,35 @@ use uuid::Uuid;
 
 use crate::config::ServerConfig;
 use crate::models::timer::{CreateTimerRequest, TimerResponse};
+use crate::services::database_server::DatabaseClusters;
 use crate::services::timer::{TimerService, to_response};
+use crate::weather::WeatherCache;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: PgPool,
     pub config: ServerConfig,
+    pub clusters: DatabaseClusters,
+    /// Shared HTTP client for upstream APIs
+    pub http: reqwest::Client,
+    /// Weather provider behind the current-conditions cache
+    pub weather: WeatherCache,
+}
+
+/// The API token that authenticated the current request, inserted by the auth middleware
+#[derive(Clone)]
+pub struct ApiToken(pub String);
+
+impl ApiToken {
+    /// Stable identifier for the token that can be stored without exposing the token itself
+    pub fn fingerprint(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        Sha256::digest(self.0.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
 }
 
 /// Create a new timer
@@ -1,39 +1,124 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     response::IntoResponse,
     Json,
 };
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::controllers::timer::{AppError, AppState};
+use crate::models::weather::HistoryInterval;
 use crate::services::weather::WeatherService;
+use crate::weather::units::UnitSystem;
+
+#[derive(Debug, Deserialize)]
+pub struct UnitsQuery {
+    /// `metric` (default), `imperial` or `si`
+    #[serde(default)]
+    units: UnitSystem,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WeatherListQuery {
+    /// Only include the locations in this group, in group order
+    group: Option<String>,
+    /// `metric` (default), `imperial` or `si`
+    #[serde(default)]
+    units: UnitSystem,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WeatherHistoryQuery {
+    /// Start of the range (default: 7 days before `to` for hourly, 30 days for daily)
+    from: Option<DateTime<Utc>>,
+    /// End of the range (default: now)
+    to: Option<DateTime<Utc>>,
+    /// `hourly` (default) or `daily`
+    #[serde(default)]
+    interval: HistoryInterval,
+    /// `metric` (default), `imperial` or `si`
+    #[serde(default)]
+    units: UnitSystem,
+}
 
 /// Get weather for a specific location
 pub async fn get_weather_for_location(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
+    Query(query): Query<UnitsQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let api_key = state
-        .config
-        .weather_api_key
-        .as_ref()
-        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Weather API key not configured")))?;
-
-    let weather = WeatherService::get_for_location(&state.db, id, api_key).await?;
+    let weather =
+        WeatherService::get_for_location(&state.db, id, &state.weather, query.units).await?;
     Ok(Json(weather))
 }
 
-/// Get weather for all locations
+/// Get weather for all locations (or a group's locations, with `?group=`)
 pub async fn get_weather_for_all_locations(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<WeatherListQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let api_key = state
-        .config
-        .weather_api_key
-        .as_ref()
-        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Weather API key not configured")))?;
-
-    let weather_list = WeatherService::get_for_all_locations(&state.db, api_key).await?;
+    let weather_list = WeatherService::get_for_all_locations(
+        &state.db,
+        query.group.as_deref(),
+        &state.weather,
+        query.units,
+    )
+    .await?
+    .ok_or(AppError::NotFound)?;
     Ok(Json(weather_list))
 }
+
+/// Get the hourly forecast (next 48 hours) for a location
+pub async fn get_hourly_forecast(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<UnitsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let forecast = WeatherService::hourly_forecast(&state.db, id, &state.weather, query.units)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(forecast))
+}
+
+/// Get the daily forecast (next 7 days) for a location
+pub async fn get_daily_forecast(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<UnitsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let forecast = WeatherService::daily_forecast(&state.db, id, &state.weather, query.units)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(forecast))
+}
+
+/// Get current air quality and UV index for a location
+pub async fn get_air_quality(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let air_quality = WeatherService::air_quality(&state.db, id, &state.weather)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(air_quality))
+}
+
+/// Get recorded weather for a location, aggregated per hour or day
+pub async fn get_weather_history(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<WeatherHistoryQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let to = query.to.unwrap_or_else(Utc::now);
+    let from = query.from.unwrap_or_else(|| match query.interval {
+        HistoryInterval::Hourly => to - Duration::days(7),
+        HistoryInterval::Daily => to - Duration::days(30),
+    });
+
+    let history = WeatherService::history(&state.db, id, from, to, query.interval, query.units)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(history))
+}
@@ -0,0 +1,69 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::controllers::timer::{AppError, AppState};
+use crate::models::weather_alert::{AlertStatus, CreateWeatherRuleRequest};
+use crate::services::weather_alert::WeatherAlertService;
+
+/// Alerts returned when the query does not ask for a number
+const DEFAULT_ALERT_LIMIT: i64 = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct WeatherRuleListQuery {
+    /// Only rules for this location
+    location_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WeatherAlertListQuery {
+    /// `firing` or `resolved` (default: both)
+    status: Option<AlertStatus>,
+    /// Most alerts to return (default 50)
+    limit: Option<i64>,
+}
+
+/// Create a weather rule
+pub async fn create_weather_rule(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<CreateWeatherRuleRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let rule = WeatherAlertService::create_rule(&state.db, req).await?;
+    Ok((StatusCode::CREATED, Json(rule)))
+}
+
+/// List weather rules (or one location's, with `?location_id=`)
+pub async fn list_weather_rules(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<WeatherRuleListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let rules = WeatherAlertService::list_rules(&state.db, query.location_id).await?;
+    Ok(Json(rules))
+}
+
+/// Delete a weather rule and its alerts
+pub async fn delete_weather_rule(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let rule = WeatherAlertService::delete_rule(&state.db, id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(rule))
+}
+
+/// List weather alerts, most recent first
+pub async fn list_weather_alerts(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<WeatherAlertListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_ALERT_LIMIT).max(1);
+    let alerts = WeatherAlertService::list_alerts(&state.db, query.status, limit).await?;
+    Ok(Json(alerts))
+}
@@ -0,0 +1,252 @@
+use anyhow::{Context, Result};
+use std::sync::OnceLock;
+
+use crate::location::Coordinates;
+
+/// Bundled city dataset, see the header of the file for its columns
+const CITIES: &str = include_str!("../data/cities.tsv");
+
+/// Shortest query that is matched as a name prefix or with typos
+const MIN_FUZZY_LENGTH: usize = 3;
+
+/// A city from the bundled dataset
+#[derive(Debug, Clone)]
+pub struct City {
+    pub name: String,
+    /// ISO 3166-1 alpha-2 country code
+    pub country: String,
+    /// First-level administrative region (state, province, ...)
+    pub admin: Option<String>,
+    pub population: u64,
+    pub coordinates: Coordinates,
+    /// Normalised name, ASCII name and alternate names used for matching
+    keys: Vec<String>,
+}
+
+impl City {
+    fn parse(line: &str) -> Result<City> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [name, ascii_name, alternate_names, country, admin, population, latitude, longitude] =
+            fields[..]
+        else {
+            anyhow::bail!("Expected 8 columns, found {}", fields.len());
+        };
+
+        let mut keys: Vec<String> = [name, ascii_name]
+            .into_iter()
+            .chain(alternate_names.split(','))
+            .map(normalize)
+            .filter(|key| !key.is_empty())
+            .collect();
+        keys.dedup();
+
+        Ok(City {
+            name: name.to_string(),
+            country: country.to_string(),
+            admin: Some(admin.to_string()).filter(|admin| !admin.is_empty()),
+            population: population.parse()?,
+            coordinates: Coordinates::new(latitude.parse()?, longitude.parse()?)?,
+            keys,
+        })
+    }
+
+    /// How well the city's names match a normalised query, if at all
+    fn match_name(&self, query: &str) -> Option<NameMatch> {
+        self.keys
+            .iter()
+            .filter_map(|key| NameMatch::between(key, query))
+            .min()
+    }
+
+    /// Whether a normalised qualifier names the city's country code or region
+    fn in_region(&self, qualifier: &str) -> bool {
+        if normalize(&self.country) == qualifier {
+            return true;
+        }
+
+        self.admin.as_deref().map(normalize).is_some_and(|admin| {
+            admin == qualifier
+                || (qualifier.len() >= MIN_FUZZY_LENGTH && admin.starts_with(qualifier))
+        })
+    }
+}
+
+/// How a city name matched a query, best first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum NameMatch {
+    Exact,
+    Prefix,
+    /// Within the given number of single-character edits
+    Fuzzy(usize),
+}
+
+impl NameMatch {
+    fn between(key: &str, query: &str) -> Option<NameMatch> {
+        if key == query {
+            return Some(NameMatch::Exact);
+        }
+
+        let length = query.chars().count();
+        if length < MIN_FUZZY_LENGTH {
+            return None;
+        }
+        if key.starts_with(query) {
+            return Some(NameMatch::Prefix);
+        }
+
+        // Allow one typo per four characters
+        let distance = edit_distance(key, query);
+        (distance <= (length / 4).max(1)).then_some(NameMatch::Fuzzy(distance))
+    }
+}
+
+/// The bundled cities, parsed on first use
+pub fn cities() -> &'static [City] {
+    static PARSED: OnceLock<Vec<City>> = OnceLock::new();
+
+    PARSED.get_or_init(|| {
+        CITIES
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(index, line)| {
+                City::parse(line).with_context(|| format!("cities.tsv line {}", index + 1))
+            })
+            .collect::<Result<_>>()
+            .expect("bundled city dataset is valid")
+    })
+}
+
+/// Find cities matching a query such as `Portland`, `Portland, Maine` or `Paris, FR`.
+///
+/// Anything after the first comma narrows the results by country code or
+/// region. Exact name matches come first, then prefix and fuzzy matches; ties
+/// are broken by population.
+pub fn search(query: &str, limit: usize) -> Vec<&'static City> {
+    let mut parts = query.split(',').map(normalize);
+    let name = parts.next().unwrap_or_default();
+    if name.is_empty() {
+        return vec![];
+    }
+    let qualifiers: Vec<String> = parts.filter(|part| !part.is_empty()).collect();
+
+    let mut matches: Vec<(NameMatch, &City)> = cities()
+        .iter()
+        .filter(|city| qualifiers.iter().all(|qualifier| city.in_region(qualifier)))
+        .filter_map(|city| city.match_name(&name).map(|quality| (quality, city)))
+        .collect();
+
+    matches.sort_by(|(a_match, a), (b_match, b)| {
+        a_match
+            .cmp(b_match)
+            .then_with(|| b.population.cmp(&a.population))
+    });
+
+    matches
+        .into_iter()
+        .take(limit)
+        .map(|(_, city)| city)
+        .collect()
+}
+
+/// The city closest to a point, with its distance in kilometres
+pub fn nearest(coordinates: &Coordinates) -> Option<(&'static City, f64)> {
+    cities()
+        .iter()
+        .map(|city| (city, city.coordinates.distance_km(coordinates)))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+}
+
+/// Lowercase, with punctuation and runs of whitespace collapsed to single spaces
+fn normalize(value: &str) -> String {
+    value
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Levenshtein distance between two strings, by character
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn first(query: &str) -> &'static City {
+        search(query, 1)
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| panic!("no match for {query}"))
+    }
+
+    #[test]
+    fn dataset_parses() {
+        assert!(cities().len() > 200);
+    }
+
+    #[test]
+    fn ranks_by_population() {
+        assert_eq!(first("Paris").country, "FR");
+        assert_eq!(first("portland").admin.as_deref(), Some("Oregon"));
+
+        let londons = search("London", 5);
+        assert_eq!(londons.len(), 2);
+        assert_eq!(londons[1].country, "CA");
+    }
+
+    #[test]
+    fn qualifiers_narrow_results() {
+        assert_eq!(first("Portland, Maine").admin.as_deref(), Some("Maine"));
+        assert_eq!(first("Paris, US").admin.as_deref(), Some("Texas"));
+        assert!(search("Berlin, FR", 5).is_empty());
+    }
+
+    #[test]
+    fn fuzzy_and_alternate_names() {
+        assert_eq!(first("Berln").name, "Berlin");
+        assert_eq!(first("münchen").name, "Munich");
+        assert_eq!(first("Zurich").name, "Zürich");
+        assert_eq!(first("sao paulo").country, "BR");
+        assert_eq!(first("St Johns").country, "CA");
+        assert!(search("Xyzzyville", 5).is_empty());
+    }
+
+    #[test]
+    fn exact_matches_beat_prefixes() {
+        // Porto Alegre is larger, but only matches as a prefix
+        assert_eq!(first("Porto").country, "PT");
+        assert_eq!(search("Porto", 2)[1].name, "Porto Alegre");
+        assert_eq!(first("Sydney, Nova Scotia").country, "CA");
+    }
+
+    #[test]
+    fn nearest_city() {
+        let potsdam = Coordinates::new(52.3906, 13.0645).unwrap();
+        let (city, distance) = nearest(&potsdam).unwrap();
+        assert_eq!(city.name, "Berlin");
+        assert!((distance - 27.0).abs() < 3.0, "distance was {distance}");
+    }
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("berlin", "berln"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
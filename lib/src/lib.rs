@@ -1,10 +1,16 @@
 pub mod config;
 pub mod controllers;
+pub mod crypto;
 pub mod db;
+pub mod gazetteer;
 pub mod location;
 pub mod models;
 pub mod nomenclator;
 pub mod services;
 pub mod slug;
+pub mod solar;
+pub mod sql;
+pub mod timezone;
 pub mod uuid;
+pub mod weather;
 pub mod ws;
@@ -1,32 +1,247 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
+use std::fmt;
 use uuid::Uuid;
 
+/// Mean radius of the Earth in kilometres
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// A validated latitude/longitude pair, in degrees (north and east positive)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawCoordinates")]
+pub struct Coordinates {
+    latitude: f64,
+    longitude: f64,
+}
+
+#[derive(Deserialize)]
+struct RawCoordinates {
+    latitude: f64,
+    longitude: f64,
+}
+
+impl TryFrom<RawCoordinates> for Coordinates {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: RawCoordinates) -> Result<Self> {
+        Coordinates::new(raw.latitude, raw.longitude)
+    }
+}
+
+impl Coordinates {
+    pub fn new(latitude: f64, longitude: f64) -> Result<Self> {
+        if !latitude.is_finite() || !(-90.0..=90.0).contains(&latitude) {
+            anyhow::bail!("Latitude {} is out of range (-90 to 90)", latitude);
+        }
+        if !longitude.is_finite() || !(-180.0..=180.0).contains(&longitude) {
+            anyhow::bail!("Longitude {} is out of range (-180 to 180)", longitude);
+        }
+
+        Ok(Self {
+            latitude,
+            longitude,
+        })
+    }
+
+    /// Build coordinates from optional parts; both or neither must be given
+    pub fn from_parts(latitude: Option<f64>, longitude: Option<f64>) -> Result<Option<Self>> {
+        match (latitude, longitude) {
+            (Some(latitude), Some(longitude)) => Ok(Some(Self::new(latitude, longitude)?)),
+            (None, None) => Ok(None),
+            _ => anyhow::bail!("Latitude and longitude must be given together"),
+        }
+    }
+
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /// Great-circle distance to `other` in kilometres (haversine formula)
+    pub fn distance_km(&self, other: &Coordinates) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+
+    /// Initial compass bearing towards `other`, in degrees from north (0 to 360)
+    pub fn bearing_degrees(&self, other: &Coordinates) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let d_lon = (other.longitude - self.longitude).to_radians();
+
+        let y = d_lon.sin() * lat2.cos();
+        let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * d_lon.cos();
+        y.atan2(x).to_degrees().rem_euclid(360.0)
+    }
+}
+
+impl fmt::Display for Coordinates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.latitude, self.longitude)
+    }
+}
+
+/// A named place with a timezone and, optionally, coordinates.
+///
+/// Serialized with `latitude` and `longitude` fields that are null without coordinates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "RawLocation", try_from = "RawLocation")]
 pub struct Location {
     pub id: Uuid,
     pub name: String,
-    pub longitude: f64,
-    pub latitude: f64,
+    pub timezone: String,
+    pub coordinates: Option<Coordinates>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawLocation {
+    id: Uuid,
+    name: String,
+    timezone: String,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<RawLocation> for Location {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: RawLocation) -> Result<Self> {
+        Ok(Location {
+            id: raw.id,
+            name: raw.name,
+            timezone: raw.timezone,
+            coordinates: Coordinates::from_parts(raw.latitude, raw.longitude)?,
+            created_at: raw.created_at,
+        })
+    }
+}
+
+impl From<Location> for RawLocation {
+    fn from(location: Location) -> Self {
+        RawLocation {
+            id: location.id,
+            name: location.name,
+            timezone: location.timezone,
+            latitude: location.coordinates.map(|c| c.latitude()),
+            longitude: location.coordinates.map(|c| c.longitude()),
+            created_at: location.created_at,
+        }
+    }
 }
 
 impl Location {
-    pub fn new(name: &str, longitude: f64, latitude: f64) -> Location {
+    pub fn new(name: &str, timezone: &str, coordinates: Option<Coordinates>) -> Location {
         Location {
             id: Uuid::new_v4(),
             name: name.to_string(),
-            longitude,
-            latitude,
+            timezone: timezone.to_string(),
+            coordinates,
+            created_at: Utc::now(),
         }
     }
 }
 
+impl<'r> FromRow<'r, PgRow> for Location {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        let coordinates =
+            Coordinates::from_parts(row.try_get("latitude")?, row.try_get("longitude")?)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?;
+
+        Ok(Location {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            timezone: row.try_get("timezone")?,
+            coordinates,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn new_location() {
-        let location = Location::new("Test", 12.0, 4.0);
+        let coordinates = Coordinates::new(4.0, 12.0).unwrap();
+        let location = Location::new("Test", "Africa/Lagos", Some(coordinates));
         assert_eq!(location.name, "Test");
-        assert_eq!(location.longitude, 12.0);
-        assert_eq!(location.latitude, 4.0);
+        assert_eq!(location.timezone, "Africa/Lagos");
+        assert_eq!(location.coordinates.unwrap().longitude(), 12.0);
+        assert_eq!(location.coordinates.unwrap().latitude(), 4.0);
+    }
+
+    #[test]
+    fn validates_coordinates() {
+        assert!(Coordinates::new(90.0, -180.0).is_ok());
+        assert!(Coordinates::new(90.5, 0.0).is_err());
+        assert!(Coordinates::new(0.0, 181.0).is_err());
+        assert!(Coordinates::new(f64::NAN, 0.0).is_err());
+        assert!(Coordinates::from_parts(Some(1.0), None).is_err());
+        assert_eq!(Coordinates::from_parts(None, None).unwrap(), None);
+    }
+
+    #[test]
+    fn distance_and_bearing() {
+        let berlin = Coordinates::new(52.52, 13.405).unwrap();
+        let new_york = Coordinates::new(40.7128, -74.006).unwrap();
+
+        let distance = berlin.distance_km(&new_york);
+        assert!((distance - 6385.0).abs() < 10.0, "distance was {distance}");
+
+        let bearing = berlin.bearing_degrees(&new_york);
+        assert!((bearing - 295.6).abs() < 1.0, "bearing was {bearing}");
+
+        let north = Coordinates::new(53.52, 13.405).unwrap();
+        assert!(berlin.bearing_degrees(&north).abs() < 1e-9);
+    }
+
+    #[test]
+    fn serializes_flat_coordinates() {
+        let location = Location::new(
+            "Berlin",
+            "Europe/Berlin",
+            Coordinates::new(52.52, 13.405).ok(),
+        );
+        let json = serde_json::to_value(&location).unwrap();
+        assert_eq!(json["latitude"], 52.52);
+        assert_eq!(json["longitude"], 13.405);
+
+        let parsed: Location = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.coordinates, location.coordinates);
+    }
+
+    #[test]
+    fn serializes_missing_coordinates_as_null() {
+        let location = Location::new("Nowhere", "UTC", None);
+        let json = serde_json::to_value(&location).unwrap();
+        assert!(json["latitude"].is_null());
+        assert!(json["longitude"].is_null());
+        assert!(json.as_object().unwrap().contains_key("latitude"));
+
+        let parsed: Location = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.coordinates, None);
+    }
+
+    #[test]
+    fn rejects_invalid_coordinates() {
+        let mut json = serde_json::to_value(Location::new("Bad", "UTC", None)).unwrap();
+        json["latitude"] = 91.0.into();
+        json["longitude"] = 0.0.into();
+        assert!(serde_json::from_value::<Location>(json.clone()).is_err());
+
+        json["longitude"] = serde_json::Value::Null;
+        assert!(serde_json::from_value::<Location>(json).is_err());
     }
 }
@@ -10,6 +10,10 @@ pub struct ManagedDatabase {
     pub created_at: DateTime<Utc>,
     pub owner: Option<String>,
     pub notes: Option<String>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub trashed_name: Option<String>,
+    pub protected: bool,
+    pub server: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -17,4 +21,98 @@ pub struct CreateDatabaseRequest {
     pub db_name: String,
     pub owner: Option<String>,
     pub notes: Option<String>,
+    #[serde(default)]
+    pub protected: bool,
+    /// Registered database server to create the database on (defaults to the control cluster)
+    pub server: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetProtectionRequest {
+    pub protected: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExecSqlRequest {
+    pub sql: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatementResult {
+    pub statement: String,
+    pub rows_affected: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExecSqlResponse {
+    pub db_name: String,
+    pub committed: bool,
+    pub statements: Vec<StatementResult>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MigrationFile {
+    pub name: String,
+    pub sql: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MigrateRequest {
+    pub migrations: Vec<MigrationFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MigrationStatus {
+    Applied,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MigrationResult {
+    pub name: String,
+    pub status: MigrationStatus,
+    pub statements: Vec<StatementResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MigrateResponse {
+    pub db_name: String,
+    pub migrations: Vec<MigrationResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DatabaseConnectionInfo {
+    pub db_name: String,
+    pub server: Option<String>,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: Option<String>,
+    pub database_url: String,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DatabaseServer {
+    pub id: Uuid,
+    pub name: String,
+    pub admin_url_encrypted: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateDatabaseServerRequest {
+    pub name: String,
+    pub admin_url: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DatabaseServerResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    pub created_at: DateTime<Utc>,
 }
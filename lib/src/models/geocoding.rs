@@ -1,15 +1,31 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::location::Coordinates;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GeocodingResult {
     pub city_name: String,
     pub country: String,
     pub state: Option<String>,
-    pub latitude: f32,
-    pub longitude: f32,
+    #[serde(flatten)]
+    pub coordinates: Coordinates,
     pub timezone: String,
 }
 
+/// Candidates for a forward lookup, and where they came from
+#[derive(Debug)]
+pub struct GeocodingLookup {
+    pub results: Vec<GeocodingResult>,
+    /// When the results were cached, if they were served from the cache
+    pub cached_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PurgeGeocodingCacheResponse {
+    pub purged: u64,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct OpenWeatherGeoResponse {
     pub name: String,
@@ -18,3 +34,23 @@ pub struct OpenWeatherGeoResponse {
     pub country: String,
     pub state: Option<String>,
 }
+
+impl TryFrom<&OpenWeatherGeoResponse> for Coordinates {
+    type Error = anyhow::Error;
+
+    fn try_from(geo: &OpenWeatherGeoResponse) -> anyhow::Result<Self> {
+        Coordinates::new(geo.lat, geo.lon)
+    }
+}
+
+/// The place nearest to a point, and the timezone at the point itself
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReverseGeocodingResult {
+    #[serde(flatten)]
+    pub coordinates: Coordinates,
+    pub timezone: String,
+    /// Nearest named place, if any is known around the point
+    pub place: Option<GeocodingResult>,
+    /// Distance from the point to `place`
+    pub distance_km: Option<f64>,
+}
@@ -1,24 +1,35 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::collections::HashMap;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
-pub struct Location {
-    pub id: Uuid,
-    pub name: String,
-    pub timezone: String,
-    pub latitude: Option<f32>,
-    pub longitude: Option<f32>,
-    pub created_at: DateTime<Utc>,
-}
+pub use crate::location::{Coordinates, Location};
+use crate::solar::SolarTimes;
+use crate::timezone::{Interval, LocalTimeStatus, TimeFormat};
 
 #[derive(Debug, Deserialize)]
 pub struct CreateLocationRequest {
     pub name: String,
     pub timezone: String,
-    pub latitude: Option<f32>,
-    pub longitude: Option<f32>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateLocationRequest {
+    pub name: Option<String>,
+    pub timezone: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Re-derive the timezone from the (updated) coordinates
+    #[serde(default)]
+    pub derive_timezone: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetDefaultLocationRequest {
+    pub location_id: Uuid,
 }
 
 #[derive(Debug, Serialize)]
@@ -26,4 +37,136 @@ pub struct LocationTimeResponse {
     pub location: Location,
     pub current_time: DateTime<Utc>,
     pub formatted_time: String,
+    /// Current UTC offset, e.g. `+02:00`
+    pub utc_offset: String,
+    pub utc_offset_seconds: i32,
+    pub is_dst: bool,
+    /// How far ahead of the caller's default location this location is (negative if behind)
+    pub offset_from_default_seconds: Option<i32>,
+    /// Sunrise, sunset and twilight for the local date (requires coordinates)
+    pub solar: Option<SolarTimes>,
+}
+
+/// Working hours for a location, as local wall-clock times
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WorkingHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+#[derive(Debug)]
+pub struct OverlapRequest {
+    pub date: NaiveDate,
+    /// Locations to plan for (all locations if empty)
+    pub location_ids: Vec<Uuid>,
+    /// Location whose calendar day is planned (the first location if not set)
+    pub reference: Option<Uuid>,
+    pub default_hours: WorkingHours,
+    /// Per-location overrides of `default_hours`
+    pub hours: HashMap<Uuid, WorkingHours>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LocationWorkingHours {
+    pub location: Location,
+    pub hours: WorkingHours,
+    /// The location's working hours on the planned day, in UTC
+    pub windows: Vec<Interval>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LocalSlot {
+    pub location_id: Uuid,
+    pub name: String,
+    pub start: String,
+    pub end: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OverlapSlot {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub duration_minutes: i64,
+    /// The slot in each location's local time
+    pub local: Vec<LocalSlot>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OverlapResponse {
+    pub date: NaiveDate,
+    pub reference_timezone: String,
+    pub day_start: DateTime<Utc>,
+    pub day_end: DateTime<Utc>,
+    pub locations: Vec<LocationWorkingHours>,
+    pub slots: Vec<OverlapSlot>,
+}
+
+/// Rendering options for the time endpoints
+#[derive(Debug, Default)]
+pub struct TimeOptions {
+    pub format: TimeFormat,
+    /// Location to compare UTC offsets against (the caller's default location)
+    pub reference: Option<Location>,
+}
+
+#[derive(Debug)]
+pub struct ConvertTimeRequest {
+    pub time: NaiveDateTime,
+    /// Source location ID or IANA timezone name
+    pub from: String,
+    /// Target locations (all locations if empty)
+    pub to: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConvertedTime {
+    pub location: Location,
+    pub formatted_time: String,
+    /// The second occurrence when the source time is ambiguous
+    pub alternative_formatted_time: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConvertTimeResponse {
+    pub time: NaiveDateTime,
+    pub from_timezone: String,
+    pub status: LocalTimeStatus,
+    pub instant: DateTime<Utc>,
+    pub alternative_instant: Option<DateTime<Utc>>,
+    pub conversions: Vec<ConvertedTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LocationGroup {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLocationGroupRequest {
+    pub name: String,
+    /// Initial members, in display order
+    #[serde(default)]
+    pub location_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetGroupLocationsRequest {
+    /// Members in display order, replacing the current membership
+    pub location_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddGroupLocationRequest {
+    pub location_id: Uuid,
+    /// Zero-based position in the group (appended if not set)
+    pub position: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LocationGroupResponse {
+    #[serde(flatten)]
+    pub group: LocationGroup,
+    pub locations: Vec<Location>,
 }
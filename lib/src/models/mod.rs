@@ -6,4 +6,6 @@ pub mod task;
 pub mod template;
 pub mod database;
 pub mod weather;
+pub mod weather_alert;
 pub mod geocoding;
+pub mod team;
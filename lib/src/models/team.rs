@@ -0,0 +1,56 @@
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A team member working from a location or a raw timezone
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TeamMember {
+    pub id: Uuid,
+    pub name: String,
+    pub email: Option<String>,
+    pub location_id: Option<Uuid>,
+    pub location_name: Option<String>,
+    /// The linked location's timezone, or the member's own
+    pub timezone: String,
+    pub work_start: NaiveTime,
+    pub work_end: NaiveTime,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTeamMemberRequest {
+    pub name: String,
+    pub email: Option<String>,
+    /// Location the member works from (mutually exclusive with `timezone`)
+    pub location_id: Option<Uuid>,
+    pub timezone: Option<String>,
+    /// Local start of the working day (default 09:00)
+    pub work_start: Option<NaiveTime>,
+    /// Local end of the working day (default 17:00)
+    pub work_end: Option<NaiveTime>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTeamMemberRequest {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    /// Link the member to a location, replacing any raw timezone
+    pub location_id: Option<Uuid>,
+    /// Use a raw timezone, unlinking any location
+    pub timezone: Option<String>,
+    pub work_start: Option<NaiveTime>,
+    pub work_end: Option<NaiveTime>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TeamMemberTimeResponse {
+    pub member: TeamMember,
+    pub current_time: DateTime<Utc>,
+    pub formatted_time: String,
+    /// Current UTC offset, e.g. `+02:00`
+    pub utc_offset: String,
+    pub within_working_hours: bool,
+    /// When the member's working day next starts or ends
+    pub next_change: Option<DateTime<Utc>>,
+}
@@ -1,7 +1,12 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::weather::air::{AqiCategory, UvRisk};
+use crate::weather::units::{UnitLabels, UnitSystem};
+
+#[derive(Debug, Serialize)]
 pub struct WeatherResponse {
     pub location_id: Uuid,
     pub location_name: String,
@@ -12,6 +17,132 @@ pub struct WeatherResponse {
     pub description: String,
     pub wind_speed_ms: f32,
     pub wind_speed_mph: f32,
+    /// Unit system of the fields below, with labels for display
+    pub units: UnitSystem,
+    pub unit_labels: UnitLabels,
+    pub temperature: f32,
+    pub feels_like: f32,
+    pub wind_speed: f32,
+    /// Direction the wind blows from, in degrees clockwise from north
+    pub wind_direction_degrees: Option<u16>,
+    pub pressure: Option<f32>,
+    pub visibility: Option<f32>,
+    /// Cloud cover, 0-100%
+    pub cloud_cover: Option<u8>,
+    /// Today's sunrise and sunset, in the location's timezone
+    pub sunrise: Option<DateTime<FixedOffset>>,
+    pub sunset: Option<DateTime<FixedOffset>>,
+    /// When this reading was fetched from the provider
+    pub cached_at: DateTime<Utc>,
+    /// The reading is past its freshness window and is being refreshed
+    pub stale: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HourlyForecastResponse {
+    pub location_id: Uuid,
+    pub location_name: String,
+    pub units: UnitSystem,
+    pub unit_labels: UnitLabels,
+    pub hours: Vec<HourlyForecastEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HourlyForecastEntry {
+    /// Start of the hour, in the location's timezone
+    pub time: DateTime<FixedOffset>,
+    pub temperature_celsius: f32,
+    pub temperature_fahrenheit: f32,
+    /// Temperature in the response's unit system
+    pub temperature: f32,
+    /// Chance of precipitation, 0-100%
+    pub precipitation_probability: u8,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DailyForecastResponse {
+    pub location_id: Uuid,
+    pub location_name: String,
+    pub units: UnitSystem,
+    pub unit_labels: UnitLabels,
+    pub days: Vec<DailyForecastEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DailyForecastEntry {
+    /// Calendar day in the location's timezone
+    pub date: NaiveDate,
+    pub temperature_min_celsius: f32,
+    pub temperature_max_celsius: f32,
+    pub temperature_min_fahrenheit: f32,
+    pub temperature_max_fahrenheit: f32,
+    /// Temperatures in the response's unit system
+    pub temperature_min: f32,
+    pub temperature_max: f32,
+    /// Highest chance of precipitation during the day, 0-100%
+    pub precipitation_probability: u8,
+    pub description: String,
+}
+
+/// Current air quality for a location; pollutant concentrations are in µg/m³
+#[derive(Debug, Serialize)]
+pub struct AirQualityResponse {
+    pub location_id: Uuid,
+    pub location_name: String,
+    pub pm2_5: f32,
+    pub pm10: f32,
+    pub ozone: f32,
+    pub nitrogen_dioxide: f32,
+    /// European AQI category of the worst pollutant
+    pub category: AqiCategory,
+    /// `None` if the weather provider does not report UV
+    pub uv_index: Option<f32>,
+    pub uv_risk: Option<UvRisk>,
+}
+
+/// Bucket size for aggregated weather history
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryInterval {
+    #[default]
+    Hourly,
+    Daily,
+}
+
+impl HistoryInterval {
+    /// Unit for Postgres `date_trunc`
+    pub fn date_trunc_unit(&self) -> &'static str {
+        match self {
+            HistoryInterval::Hourly => "hour",
+            HistoryInterval::Daily => "day",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct WeatherHistoryResponse {
+    pub location_id: Uuid,
+    pub location_name: String,
+    pub interval: HistoryInterval,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub units: UnitSystem,
+    pub unit_labels: UnitLabels,
+    pub points: Vec<WeatherHistoryPoint>,
+}
+
+/// Aggregated observations for one hour or day (days and hours in the location's timezone),
+/// in the response's unit system
+#[derive(Debug, Serialize, FromRow)]
+pub struct WeatherHistoryPoint {
+    pub period_start: DateTime<Utc>,
+    pub temperature_min: f32,
+    pub temperature_avg: f32,
+    pub temperature_max: f32,
+    pub humidity_avg: f32,
+    pub wind_speed_max: f32,
+    pub observations: i64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -19,6 +150,13 @@ pub struct OpenWeatherResponse {
     pub main: OpenWeatherMain,
     pub weather: Vec<OpenWeatherCondition>,
     pub wind: OpenWeatherWind,
+    /// Metres, capped at 10 km
+    #[serde(default)]
+    pub visibility: Option<f32>,
+    #[serde(default)]
+    pub clouds: Option<OpenWeatherClouds>,
+    #[serde(default)]
+    pub sys: Option<OpenWeatherSys>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -26,6 +164,9 @@ pub struct OpenWeatherMain {
     pub temp: f32,
     pub feels_like: f32,
     pub humidity: u8,
+    /// Sea-level pressure, hPa
+    #[serde(default)]
+    pub pressure: Option<f32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,4 +177,22 @@ pub struct OpenWeatherCondition {
 #[derive(Debug, Deserialize)]
 pub struct OpenWeatherWind {
     pub speed: f32,
+    /// Degrees clockwise from north
+    #[serde(default)]
+    pub deg: Option<u16>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OpenWeatherClouds {
+    /// Cloud cover, 0-100%
+    pub all: u8,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OpenWeatherSys {
+    /// Unix timestamps
+    #[serde(default)]
+    pub sunrise: Option<i64>,
+    #[serde(default)]
+    pub sunset: Option<i64>,
 }
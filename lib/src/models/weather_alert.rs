@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// What a weather rule watches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text")]
+#[serde(rename_all = "snake_case")]
+pub enum WeatherRuleKind {
+    /// Current temperature below the threshold (degrees Celsius)
+    #[sqlx(rename = "temperature_below")]
+    TemperatureBelow,
+    /// Current wind speed above the threshold (m/s)
+    #[sqlx(rename = "wind_above")]
+    WindAbove,
+    /// Rain likely within the next `threshold` hours
+    #[sqlx(rename = "rain_within")]
+    RainWithin,
+}
+
+impl std::fmt::Display for WeatherRuleKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeatherRuleKind::TemperatureBelow => write!(f, "temperature_below"),
+            WeatherRuleKind::WindAbove => write!(f, "wind_above"),
+            WeatherRuleKind::RainWithin => write!(f, "rain_within"),
+        }
+    }
+}
+
+/// A threshold rule for one location
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WeatherRule {
+    pub id: Uuid,
+    pub location_id: Uuid,
+    pub location_name: String,
+    pub kind: WeatherRuleKind,
+    pub threshold: f32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWeatherRuleRequest {
+    pub location_id: Uuid,
+    pub kind: WeatherRuleKind,
+    /// Degrees Celsius, m/s or hours, depending on `kind`
+    pub threshold: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text")]
+#[serde(rename_all = "lowercase")]
+pub enum AlertStatus {
+    #[sqlx(rename = "firing")]
+    Firing,
+    #[sqlx(rename = "resolved")]
+    Resolved,
+}
+
+/// A period during which a rule fired
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WeatherAlert {
+    pub id: Uuid,
+    pub rule_id: Uuid,
+    pub location_id: Uuid,
+    pub location_name: String,
+    pub kind: WeatherRuleKind,
+    pub threshold: f32,
+    pub status: AlertStatus,
+    /// Observed value when the rule fired (% chance for rain rules)
+    pub value: f32,
+    pub message: String,
+    pub fired_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
@@ -1,17 +1,38 @@
 use anyhow::{Context, Result};
-use sqlx::PgPool;
+use rand::distr::{Alphanumeric, SampleString};
+use sqlx::postgres::PgConnection;
+use sqlx::{Connection, Executor, PgPool};
 use uuid::Uuid;
 
-use crate::models::database::{CreateDatabaseRequest, ManagedDatabase};
+use crate::config::ServerConfig;
+use crate::crypto;
+use crate::models::database::{
+    CreateDatabaseRequest, DatabaseConnectionInfo, ExecSqlResponse, ManagedDatabase, MigrateResponse, MigrationFile,
+    MigrationResult, MigrationStatus, StatementResult,
+};
+use crate::services::database_server::{DatabaseClusters, DatabaseTarget};
+use crate::sql::split_statements;
+
+/// Table used to record which migrations have been applied to a managed database
+const MIGRATIONS_TABLE: &str = "_ctrlsys_migrations";
+
+/// Length of generated passwords for database owner roles
+const OWNER_PASSWORD_LEN: usize = 32;
 
 pub struct DatabaseService;
 
 impl DatabaseService {
     /// Create a new database and track it
-    pub async fn create(pool: &PgPool, req: CreateDatabaseRequest) -> Result<ManagedDatabase> {
+    pub async fn create(
+        pool: &PgPool,
+        clusters: &DatabaseClusters,
+        req: CreateDatabaseRequest,
+    ) -> Result<ManagedDatabase> {
         // Validate database name (only alphanumeric and underscores, must start with letter)
         Self::validate_db_name(&req.db_name)?;
 
+        let target = clusters.target(pool, req.server.as_deref()).await?;
+
         let id = Uuid::new_v4();
         let now = chrono::Utc::now();
 
@@ -20,15 +41,15 @@ impl DatabaseService {
         // So we must carefully validate the name first
         let create_db_query = format!("CREATE DATABASE \"{}\"", req.db_name);
         sqlx::query(&create_db_query)
-            .execute(pool)
+            .execute(&target.pool)
             .await
             .context("Failed to create database")?;
 
         // Track the database in our managed_databases table
         let managed_db = sqlx::query_as::<_, ManagedDatabase>(
             r#"
-            INSERT INTO managed_databases (id, db_name, created_at, owner, notes)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO managed_databases (id, db_name, created_at, owner, notes, protected, server)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             RETURNING *
             "#,
         )
@@ -37,6 +58,8 @@ impl DatabaseService {
         .bind(now)
         .bind(&req.owner)
         .bind(&req.notes)
+        .bind(req.protected)
+        .bind(&req.server)
         .fetch_one(pool)
         .await?;
 
@@ -48,6 +71,7 @@ impl DatabaseService {
         let databases = sqlx::query_as::<_, ManagedDatabase>(
             r#"
             SELECT * FROM managed_databases
+            WHERE deleted_at IS NULL
             ORDER BY created_at DESC
             "#,
         )
@@ -57,12 +81,27 @@ impl DatabaseService {
         Ok(databases)
     }
 
+    /// List databases currently in the trash
+    pub async fn list_trash(pool: &PgPool) -> Result<Vec<ManagedDatabase>> {
+        let databases = sqlx::query_as::<_, ManagedDatabase>(
+            r#"
+            SELECT * FROM managed_databases
+            WHERE deleted_at IS NOT NULL
+            ORDER BY deleted_at DESC
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(databases)
+    }
+
     /// Get a specific managed database by name
     pub async fn get_by_name(pool: &PgPool, db_name: &str) -> Result<Option<ManagedDatabase>> {
         let database = sqlx::query_as::<_, ManagedDatabase>(
             r#"
             SELECT * FROM managed_databases
-            WHERE db_name = $1
+            WHERE db_name = $1 AND deleted_at IS NULL
             "#,
         )
         .bind(db_name)
@@ -87,44 +126,246 @@ impl DatabaseService {
         Ok(database)
     }
 
-    /// Drop a database and remove it from tracking
-    pub async fn drop(pool: &PgPool, db_name: &str) -> Result<ManagedDatabase> {
-        // Safety check: prevent dropping certain important databases
-        let protected_databases = ["postgres", "template0", "template1", "ctrlsys"];
-        if protected_databases.contains(&db_name) {
-            anyhow::bail!("Cannot drop protected database: {}", db_name);
-        }
-
+    /// Move a database to the trash.
+    ///
+    /// The database is renamed out of the way and marked deleted; it can be restored with
+    /// `undelete` until the grace period elapses and `purge_expired_trash` drops it.
+    pub async fn trash(
+        pool: &PgPool,
+        clusters: &DatabaseClusters,
+        config: &ServerConfig,
+        db_name: &str,
+    ) -> Result<ManagedDatabase> {
         // Verify the database is tracked
         let managed_db = Self::get_by_name(pool, db_name)
             .await?
             .context("Database not found in managed databases")?;
 
+        Self::check_not_protected(config, &managed_db)?;
+
         // Validate database name (extra safety)
         Self::validate_db_name(db_name)?;
 
-        // Terminate existing connections to the database
-        let terminate_query = format!(
-            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE datname = '{}'",
-            db_name
+        let target = clusters.target(pool, managed_db.server.as_deref()).await?;
+        let trashed_name = format!("_trash_{}", managed_db.id.simple());
+
+        // A database cannot be renamed while clients are connected to it
+        Self::terminate_connections(&target.pool, db_name).await?;
+
+        let rename_query = format!(
+            "ALTER DATABASE \"{}\" RENAME TO \"{}\"",
+            db_name, trashed_name
+        );
+        sqlx::query(&rename_query)
+            .execute(&target.pool)
+            .await
+            .context("Failed to move database to trash")?;
+
+        let managed_db = sqlx::query_as::<_, ManagedDatabase>(
+            r#"
+            UPDATE managed_databases
+            SET deleted_at = $1, trashed_name = $2
+            WHERE id = $3
+            RETURNING *
+            "#,
+        )
+        .bind(chrono::Utc::now())
+        .bind(&trashed_name)
+        .bind(managed_db.id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(managed_db)
+    }
+
+    /// Restore the most recently trashed database with this name, if still within the grace period
+    pub async fn undelete(
+        pool: &PgPool,
+        clusters: &DatabaseClusters,
+        db_name: &str,
+        grace_period: chrono::Duration,
+    ) -> Result<ManagedDatabase> {
+        let cutoff = chrono::Utc::now() - grace_period;
+
+        let trashed_db = sqlx::query_as::<_, ManagedDatabase>(
+            r#"
+            SELECT * FROM managed_databases
+            WHERE db_name = $1 AND deleted_at IS NOT NULL AND deleted_at > $2
+            ORDER BY deleted_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(db_name)
+        .bind(cutoff)
+        .fetch_optional(pool)
+        .await?
+        .context("No recoverable database with that name in the trash")?;
+
+        let target = clusters.target(pool, trashed_db.server.as_deref()).await?;
+
+        if Self::get_by_name(pool, db_name).await?.is_some()
+            || Self::exists(&target.pool, db_name).await?
+        {
+            anyhow::bail!("A database named '{}' already exists", db_name);
+        }
+
+        let trashed_name = trashed_db
+            .trashed_name
+            .as_deref()
+            .context("Trashed database has no trash name recorded")?;
+
+        Self::validate_db_name(db_name)?;
+        Self::validate_db_name(trashed_name)?;
+
+        let rename_query = format!(
+            "ALTER DATABASE \"{}\" RENAME TO \"{}\"",
+            trashed_name, db_name
         );
-        sqlx::query(&terminate_query).execute(pool).await?;
+        sqlx::query(&rename_query)
+            .execute(&target.pool)
+            .await
+            .context("Failed to restore database from trash")?;
+
+        let managed_db = sqlx::query_as::<_, ManagedDatabase>(
+            r#"
+            UPDATE managed_databases
+            SET deleted_at = NULL, trashed_name = NULL
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(trashed_db.id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(managed_db)
+    }
+
+    /// Permanently drop trashed databases whose grace period has elapsed
+    pub async fn purge_expired_trash(
+        pool: &PgPool,
+        clusters: &DatabaseClusters,
+        grace_period: chrono::Duration,
+    ) -> Result<Vec<ManagedDatabase>> {
+        let cutoff = chrono::Utc::now() - grace_period;
+
+        let expired = sqlx::query_as::<_, ManagedDatabase>(
+            r#"
+            SELECT * FROM managed_databases
+            WHERE deleted_at IS NOT NULL AND deleted_at <= $1
+            "#,
+        )
+        .bind(cutoff)
+        .fetch_all(pool)
+        .await?;
+
+        let mut purged = Vec::new();
+
+        // One database failing to drop shouldn't hold up the rest
+        for managed_db in expired {
+            if let Err(e) = Self::purge_trashed(pool, clusters, &managed_db).await {
+                tracing::warn!(
+                    "Failed to purge trashed database '{}': {:?}",
+                    managed_db.db_name,
+                    e
+                );
+                continue;
+            }
+
+            purged.push(managed_db);
+        }
+
+        Ok(purged)
+    }
+
+    /// Drop a trashed database from its server and stop tracking it
+    async fn purge_trashed(
+        pool: &PgPool,
+        clusters: &DatabaseClusters,
+        managed_db: &ManagedDatabase,
+    ) -> Result<()> {
+        if let Some(trashed_name) = &managed_db.trashed_name {
+            Self::validate_db_name(trashed_name)?;
+
+            let target = clusters.target(pool, managed_db.server.as_deref()).await?;
+            Self::terminate_connections(&target.pool, trashed_name).await?;
+
+            let drop_db_query = format!("DROP DATABASE IF EXISTS \"{}\"", trashed_name);
+            sqlx::query(&drop_db_query)
+                .execute(&target.pool)
+                .await
+                .context("Failed to purge trashed database")?;
+
+            Self::drop_owner_role(pool, &target, managed_db).await;
+        }
+
+        sqlx::query(
+            r#"
+            DELETE FROM managed_databases
+            WHERE id = $1
+            "#,
+        )
+        .bind(managed_db.id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Drop a database immediately and remove it from tracking, including one in the trash
+    pub async fn drop(
+        pool: &PgPool,
+        clusters: &DatabaseClusters,
+        config: &ServerConfig,
+        db_name: &str,
+    ) -> Result<ManagedDatabase> {
+        // Verify the database is tracked, falling back to the most recently trashed one
+        let managed_db = match Self::get_by_name(pool, db_name).await? {
+            Some(managed_db) => managed_db,
+            None => sqlx::query_as::<_, ManagedDatabase>(
+                r#"
+                SELECT * FROM managed_databases
+                WHERE db_name = $1 AND deleted_at IS NOT NULL
+                ORDER BY deleted_at DESC
+                LIMIT 1
+                "#,
+            )
+            .bind(db_name)
+            .fetch_optional(pool)
+            .await?
+            .context("Database not found in managed databases")?,
+        };
+
+        Self::check_not_protected(config, &managed_db)?;
+
+        // A trashed database lives on the server under its trash name
+        let server_db_name = managed_db.trashed_name.as_deref().unwrap_or(db_name);
+
+        // Validate database name (extra safety)
+        Self::validate_db_name(server_db_name)?;
+
+        let target = clusters.target(pool, managed_db.server.as_deref()).await?;
+
+        // Terminate existing connections to the database
+        Self::terminate_connections(&target.pool, server_db_name).await?;
 
         // Drop the actual database
-        let drop_db_query = format!("DROP DATABASE \"{}\"", db_name);
+        let drop_db_query = format!("DROP DATABASE \"{}\"", server_db_name);
         sqlx::query(&drop_db_query)
-            .execute(pool)
+            .execute(&target.pool)
             .await
             .context("Failed to drop database")?;
 
+        Self::drop_owner_role(pool, &target, &managed_db).await;
+
         // Remove from tracked databases
         sqlx::query(
             r#"
             DELETE FROM managed_databases
-            WHERE db_name = $1
+            WHERE id = $1
             "#,
         )
-        .bind(db_name)
+        .bind(managed_db.id)
         .execute(pool)
         .await?;
 
@@ -145,6 +386,358 @@ impl DatabaseService {
         Ok(result.map(|(exists,)| exists).unwrap_or(false))
     }
 
+    /// Connection settings for a managed database as its owner role, on the cluster it lives on.
+    ///
+    /// The cluster's admin credentials are never returned.
+    pub async fn connection_info(
+        pool: &PgPool,
+        clusters: &DatabaseClusters,
+        config: &ServerConfig,
+        db_name: &str,
+    ) -> Result<Option<DatabaseConnectionInfo>> {
+        let Some(managed_db) = Self::get_by_name(pool, db_name).await? else {
+            return Ok(None);
+        };
+
+        let target = clusters.target(pool, managed_db.server.as_deref()).await?;
+        let (role_name, password) = Self::owner_role(pool, &target, config, &managed_db).await?;
+
+        let info = target.connection_info(
+            &managed_db.db_name,
+            managed_db.server.clone(),
+            &role_name,
+            &password,
+        )?;
+
+        Ok(Some(info))
+    }
+
+    /// The login role that owns a managed database and its password.
+    ///
+    /// On first use a new role is created with a generated password, made owner of the
+    /// database, and recorded with the password encrypted. Roles ctrlsys did not create are
+    /// never touched: if the name is already taken on the cluster, this fails.
+    async fn owner_role(
+        pool: &PgPool,
+        target: &DatabaseTarget,
+        config: &ServerConfig,
+        managed_db: &ManagedDatabase,
+    ) -> Result<(String, String)> {
+        let secret_key = config
+            .secret_key
+            .as_deref()
+            .context("CTRLSYS_SECRET_KEY must be set to export database credentials")?;
+
+        // Lock the database's row so concurrent first requests agree on one password
+        let mut tx = pool.begin().await?;
+        sqlx::query("SELECT id FROM managed_databases WHERE id = $1 FOR UPDATE")
+            .bind(managed_db.id)
+            .execute(&mut *tx)
+            .await?;
+
+        let stored: Option<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT role_name, password_encrypted FROM managed_database_roles
+            WHERE database_id = $1
+            "#,
+        )
+        .bind(managed_db.id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some((role_name, password_encrypted)) = stored {
+            return Ok((role_name, crypto::decrypt(secret_key, &password_encrypted)?));
+        }
+
+        // The ID suffix keeps the name from matching a role that happens to share the prefix
+        let id = managed_db.id.simple().to_string();
+        let role_name = format!("{}_owner_{}", managed_db.db_name, &id[..8]);
+        if role_name.len() > 63 {
+            anyhow::bail!(
+                "Database name '{}' is too long for an owner role",
+                managed_db.db_name
+            );
+        }
+        Self::validate_db_name(&role_name)?;
+        Self::validate_db_name(&managed_db.db_name)?;
+        let password = Alphanumeric.sample_string(&mut rand::rng(), OWNER_PASSWORD_LEN);
+
+        let (role_exists,): (bool,) =
+            sqlx::query_as("SELECT EXISTS(SELECT 1 FROM pg_roles WHERE rolname = $1)")
+                .bind(&role_name)
+                .fetch_one(&target.pool)
+                .await?;
+
+        if role_exists {
+            anyhow::bail!(
+                "A role named '{}' already exists on the database server",
+                role_name
+            );
+        }
+
+        // Role names and passwords cannot be bound as parameters; the name is validated and
+        // the password is alphanumeric
+        let role_query = format!(
+            "CREATE ROLE \"{}\" WITH LOGIN PASSWORD '{}'",
+            role_name, password
+        );
+        sqlx::query(&role_query)
+            .execute(&target.pool)
+            .await
+            .context("Failed to create database owner role")?;
+
+        let owner_query = format!(
+            "ALTER DATABASE \"{}\" OWNER TO \"{}\"",
+            managed_db.db_name, role_name
+        );
+        sqlx::query(&owner_query)
+            .execute(&target.pool)
+            .await
+            .context("Failed to make the owner role own the database")?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO managed_database_roles (database_id, role_name, password_encrypted)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(managed_db.id)
+        .bind(&role_name)
+        .bind(crypto::encrypt(secret_key, &password)?)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok((role_name, password))
+    }
+
+    /// Drop the owner role `owner_role` created for a database, after the database is gone.
+    ///
+    /// Only roles recorded in `managed_database_roles` are dropped. Failures are logged
+    /// rather than returned, since the database itself has already been dropped.
+    async fn drop_owner_role(pool: &PgPool, target: &DatabaseTarget, managed_db: &ManagedDatabase) {
+        let role_name: Result<Option<String>, _> = sqlx::query_scalar(
+            "SELECT role_name FROM managed_database_roles WHERE database_id = $1",
+        )
+        .bind(managed_db.id)
+        .fetch_optional(pool)
+        .await;
+
+        let result = match role_name {
+            Ok(Some(role_name)) => sqlx::query(&format!("DROP ROLE IF EXISTS \"{}\"", role_name))
+                .execute(&target.pool)
+                .await
+                .map(|_| ()),
+            Ok(None) => Ok(()),
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to drop owner role for database '{}': {}",
+                managed_db.db_name,
+                e
+            );
+        }
+    }
+
+    /// Execute a SQL script against a managed database in a single transaction.
+    ///
+    /// Execution stops at the first failing statement and the transaction is rolled back.
+    pub async fn exec(
+        pool: &PgPool,
+        clusters: &DatabaseClusters,
+        db_name: &str,
+        sql: &str,
+    ) -> Result<ExecSqlResponse> {
+        let managed_db = Self::get_by_name(pool, db_name)
+            .await?
+            .context("Database not found in managed databases")?;
+
+        let statements = split_statements(sql);
+        let target = clusters.target(pool, managed_db.server.as_deref()).await?;
+        let mut conn = target.connect(&managed_db.db_name).await?;
+
+        let mut tx = conn.begin().await?;
+        let (results, succeeded) = Self::run_statements(&mut tx, &statements).await;
+        if succeeded {
+            tx.commit().await?;
+        } else {
+            tx.rollback().await?;
+        }
+        conn.close().await?;
+
+        Ok(ExecSqlResponse {
+            db_name: managed_db.db_name,
+            committed: succeeded,
+            statements: results,
+        })
+    }
+
+    /// Apply migrations to a managed database.
+    ///
+    /// Migrations run in name order, each in its own transaction. Applied migrations are
+    /// recorded in the target database and skipped on later runs. Migration stops at the
+    /// first failure.
+    pub async fn migrate(
+        pool: &PgPool,
+        clusters: &DatabaseClusters,
+        db_name: &str,
+        mut migrations: Vec<MigrationFile>,
+    ) -> Result<MigrateResponse> {
+        let managed_db = Self::get_by_name(pool, db_name)
+            .await?
+            .context("Database not found in managed databases")?;
+
+        let target = clusters.target(pool, managed_db.server.as_deref()).await?;
+        let mut conn = target.connect(&managed_db.db_name).await?;
+
+        let create_table_query = format!(
+            "CREATE TABLE IF NOT EXISTS {} (name TEXT PRIMARY KEY, applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW())",
+            MIGRATIONS_TABLE
+        );
+        sqlx::query(&create_table_query).execute(&mut conn).await?;
+
+        let applied_query = format!("SELECT name FROM {}", MIGRATIONS_TABLE);
+        let applied: Vec<(String,)> = sqlx::query_as(&applied_query)
+            .fetch_all(&mut conn)
+            .await?;
+        let applied: Vec<String> = applied.into_iter().map(|(name,)| name).collect();
+
+        migrations.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut results = Vec::new();
+
+        for migration in migrations {
+            if applied.contains(&migration.name) {
+                results.push(MigrationResult {
+                    name: migration.name,
+                    status: MigrationStatus::Skipped,
+                    statements: vec![],
+                });
+                continue;
+            }
+
+            let statements = split_statements(&migration.sql);
+
+            let mut tx = conn.begin().await?;
+            let (statement_results, succeeded) = Self::run_statements(&mut tx, &statements).await;
+
+            let status = if succeeded {
+                let record_query = format!("INSERT INTO {} (name) VALUES ($1)", MIGRATIONS_TABLE);
+                sqlx::query(&record_query)
+                    .bind(&migration.name)
+                    .execute(&mut *tx)
+                    .await?;
+                tx.commit().await?;
+                MigrationStatus::Applied
+            } else {
+                tx.rollback().await?;
+                MigrationStatus::Failed
+            };
+
+            results.push(MigrationResult {
+                name: migration.name,
+                status: status.clone(),
+                statements: statement_results,
+            });
+
+            if status == MigrationStatus::Failed {
+                break;
+            }
+        }
+
+        conn.close().await?;
+
+        Ok(MigrateResponse {
+            db_name: managed_db.db_name,
+            migrations: results,
+        })
+    }
+
+    /// Set or clear the drop protection flag on a managed database
+    pub async fn set_protected(
+        pool: &PgPool,
+        db_name: &str,
+        protected: bool,
+    ) -> Result<Option<ManagedDatabase>> {
+        let database = sqlx::query_as::<_, ManagedDatabase>(
+            r#"
+            UPDATE managed_databases
+            SET protected = $1
+            WHERE db_name = $2 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(protected)
+        .bind(db_name)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(database)
+    }
+
+    /// Safety check: prevent dropping databases protected by server policy or by their own flag
+    fn check_not_protected(config: &ServerConfig, managed_db: &ManagedDatabase) -> Result<()> {
+        if config.is_protected_database(&managed_db.db_name) {
+            anyhow::bail!("Cannot drop protected database: {}", managed_db.db_name);
+        }
+
+        if managed_db.protected {
+            anyhow::bail!(
+                "Database '{}' is protected; clear protection before dropping it",
+                managed_db.db_name
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Terminate all client connections to a database
+    async fn terminate_connections(pool: &PgPool, db_name: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            SELECT pg_terminate_backend(pid) FROM pg_stat_activity
+            WHERE datname = $1 AND pid <> pg_backend_pid()
+            "#,
+        )
+        .bind(db_name)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Run statements in order, stopping at the first error.
+    ///
+    /// Returns the per-statement results and whether every statement succeeded.
+    async fn run_statements(
+        conn: &mut PgConnection,
+        statements: &[String],
+    ) -> (Vec<StatementResult>, bool) {
+        let mut results = Vec::new();
+
+        for statement in statements {
+            match conn.execute(statement.as_str()).await {
+                Ok(result) => results.push(StatementResult {
+                    statement: statement.clone(),
+                    rows_affected: Some(result.rows_affected()),
+                    error: None,
+                }),
+                Err(e) => {
+                    results.push(StatementResult {
+                        statement: statement.clone(),
+                        rows_affected: None,
+                        error: Some(e.to_string()),
+                    });
+                    return (results, false);
+                }
+            }
+        }
+
+        (results, true)
+    }
+
     /// Validate database name to prevent SQL injection
     fn validate_db_name(name: &str) -> Result<()> {
         if name.is_empty() {
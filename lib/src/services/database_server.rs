@@ -0,0 +1,286 @@
+use anyhow::{Context, Result};
+use sqlx::postgres::{PgConnectOptions, PgConnection};
+use sqlx::{Connection, PgPool};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::{Mutex, OnceCell};
+use uuid::Uuid;
+
+use crate::config::ServerConfig;
+use crate::crypto;
+use crate::db;
+use crate::models::database::{
+    CreateDatabaseServerRequest, DatabaseConnectionInfo, DatabaseServer, DatabaseServerResponse,
+};
+
+pub struct DatabaseServerService;
+
+impl DatabaseServerService {
+    /// Register a Postgres cluster, verifying the admin URL before storing it encrypted
+    pub async fn create(
+        pool: &PgPool,
+        config: &ServerConfig,
+        req: CreateDatabaseServerRequest,
+    ) -> Result<DatabaseServer> {
+        let secret_key = config
+            .secret_key
+            .as_deref()
+            .context("CTRLSYS_SECRET_KEY must be set to register database servers")?;
+
+        // Make sure the cluster is reachable with the given credentials
+        let options = PgConnectOptions::from_str(&req.admin_url).context("Invalid admin URL")?;
+        let conn = PgConnection::connect_with(&options)
+            .await
+            .context(format!("Failed to connect to database server '{}'", req.name))?;
+        conn.close().await?;
+
+        let admin_url_encrypted = crypto::encrypt(secret_key, &req.admin_url)?;
+
+        let server = sqlx::query_as::<_, DatabaseServer>(
+            r#"
+            INSERT INTO database_servers (id, name, admin_url_encrypted, created_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(&req.name)
+        .bind(&admin_url_encrypted)
+        .bind(chrono::Utc::now())
+        .fetch_one(pool)
+        .await?;
+
+        Ok(server)
+    }
+
+    /// List all registered database servers
+    pub async fn list(pool: &PgPool) -> Result<Vec<DatabaseServer>> {
+        let servers = sqlx::query_as::<_, DatabaseServer>(
+            r#"
+            SELECT * FROM database_servers
+            ORDER BY name ASC
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(servers)
+    }
+
+    /// Get a registered database server by name
+    pub async fn get_by_name(pool: &PgPool, name: &str) -> Result<Option<DatabaseServer>> {
+        let server = sqlx::query_as::<_, DatabaseServer>(
+            r#"
+            SELECT * FROM database_servers WHERE name = $1
+            "#,
+        )
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(server)
+    }
+
+    /// Remove a database server from the registry.
+    ///
+    /// Fails while managed databases (including trashed ones) still live on it.
+    pub async fn delete(
+        pool: &PgPool,
+        clusters: &DatabaseClusters,
+        name: &str,
+    ) -> Result<Option<DatabaseServer>> {
+        let (in_use,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM managed_databases WHERE server = $1
+            "#,
+        )
+        .bind(name)
+        .fetch_one(pool)
+        .await?;
+
+        if in_use > 0 {
+            anyhow::bail!(
+                "Database server '{}' still hosts {} managed database(s)",
+                name,
+                in_use
+            );
+        }
+
+        let server = sqlx::query_as::<_, DatabaseServer>(
+            r#"
+            DELETE FROM database_servers
+            WHERE name = $1
+            RETURNING *
+            "#,
+        )
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+
+        clusters.evict(name).await;
+
+        Ok(server)
+    }
+
+    /// Convert a stored server to a response, exposing the host but never the credentials
+    pub fn to_response(
+        config: &ServerConfig,
+        server: DatabaseServer,
+    ) -> Result<DatabaseServerResponse> {
+        let admin_url = decrypt_admin_url(config, &server)?;
+        let options = PgConnectOptions::from_str(&admin_url)?;
+
+        Ok(DatabaseServerResponse {
+            id: server.id,
+            name: server.name,
+            host: options.get_host().to_string(),
+            port: options.get_port(),
+            created_at: server.created_at,
+        })
+    }
+}
+
+/// A Postgres cluster that managed databases can be created on
+pub struct DatabaseTarget {
+    /// Admin pool used for CREATE/ALTER/DROP DATABASE on the cluster
+    pub pool: PgPool,
+    /// Admin connection URL, used to derive connections to individual databases
+    pub admin_url: String,
+}
+
+impl DatabaseTarget {
+    /// Open a connection to a database on this cluster using the admin credentials
+    pub async fn connect(&self, db_name: &str) -> Result<PgConnection> {
+        let options = PgConnectOptions::from_str(&self.admin_url)
+            .context("Invalid database URL")?
+            .database(db_name);
+
+        PgConnection::connect_with(&options)
+            .await
+            .context(format!("Failed to connect to database '{}'", db_name))
+    }
+
+    /// Connection settings for a database on this cluster as `username`, using the admin URL's
+    /// host or unix socket but never its credentials.
+    ///
+    /// `username` and `password` must not need percent-encoding.
+    pub fn connection_info(
+        &self,
+        db_name: &str,
+        server: Option<String>,
+        username: &str,
+        password: &str,
+    ) -> Result<DatabaseConnectionInfo> {
+        let options = PgConnectOptions::from_str(&self.admin_url).context("Invalid database URL")?;
+        let port = options.get_port();
+
+        let (host, database_url) = match options.get_socket() {
+            Some(socket) => {
+                let socket = socket.display().to_string();
+                let url = format!(
+                    "postgresql://{}:{}@/{}?host={}&port={}",
+                    username,
+                    password,
+                    db_name,
+                    urlencoding::encode(&socket),
+                    port
+                );
+                (socket, url)
+            }
+            None => {
+                let host = options.get_host().to_string();
+                let url_host = if host.contains(':') {
+                    format!("[{}]", host)
+                } else {
+                    host.clone()
+                };
+                let url = format!(
+                    "postgresql://{}:{}@{}:{}/{}",
+                    username, password, url_host, port, db_name
+                );
+                (host, url)
+            }
+        };
+
+        Ok(DatabaseConnectionInfo {
+            db_name: db_name.to_string(),
+            server,
+            host,
+            port,
+            username: username.to_string(),
+            password: Some(password.to_string()),
+            database_url,
+        })
+    }
+}
+
+/// Admin connection pools for the registered database servers, created on first use
+#[derive(Clone)]
+pub struct DatabaseClusters {
+    config: ServerConfig,
+    /// One cell per server, so concurrent first requests share a single connect
+    pools: Arc<Mutex<HashMap<String, Arc<OnceCell<PgPool>>>>>,
+}
+
+impl DatabaseClusters {
+    pub fn new(config: ServerConfig) -> Self {
+        Self {
+            config,
+            pools: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Resolve the cluster a database lives on (`None` is the control cluster)
+    pub async fn target(&self, pool: &PgPool, server: Option<&str>) -> Result<DatabaseTarget> {
+        let Some(name) = server else {
+            return Ok(DatabaseTarget {
+                pool: pool.clone(),
+                admin_url: self.config.database_url.clone(),
+            });
+        };
+
+        let server = DatabaseServerService::get_by_name(pool, name)
+            .await?
+            .context(format!("Database server '{}' not found", name))?;
+        let admin_url = decrypt_admin_url(&self.config, &server)?;
+
+        let cell = self
+            .pools
+            .lock()
+            .await
+            .entry(name.to_string())
+            .or_default()
+            .clone();
+        let target_pool = cell
+            .get_or_try_init(|| async {
+                db::create_pool(&admin_url)
+                    .await
+                    .context(format!("Failed to connect to database server '{}'", name))
+            })
+            .await?
+            .clone();
+
+        Ok(DatabaseTarget {
+            pool: target_pool,
+            admin_url,
+        })
+    }
+
+    /// Close and forget the pool for a server
+    pub async fn evict(&self, name: &str) {
+        let cell = self.pools.lock().await.remove(name);
+        if let Some(pool) = cell.as_deref().and_then(OnceCell::get) {
+            pool.close().await;
+        }
+    }
+}
+
+fn decrypt_admin_url(config: &ServerConfig, server: &DatabaseServer) -> Result<String> {
+    let secret_key = config
+        .secret_key
+        .as_deref()
+        .context("CTRLSYS_SECRET_KEY must be set to use registered database servers")?;
+
+    crypto::decrypt(secret_key, &server.admin_url_encrypted)
+}
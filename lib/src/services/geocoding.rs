@@ -1,23 +1,160 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::types::Json;
+use sqlx::{FromRow, PgPool};
+use std::sync::OnceLock;
 
-use crate::models::geocoding::{GeocodingResult, OpenWeatherGeoResponse};
+use crate::config::{GeocoderKind, ServerConfig};
+use crate::gazetteer::{self, City};
+use crate::location::Coordinates;
+use crate::models::geocoding::{
+    GeocodingLookup, GeocodingResult, OpenWeatherGeoResponse, ReverseGeocodingResult,
+};
+
+/// Most candidates the OpenWeatherMap geocoding API returns for a query
+const OPENWEATHER_MAX_RESULTS: usize = 5;
+/// Provider name under which OpenWeatherMap results are cached
+const OPENWEATHER_PROVIDER: &str = "openweather";
+
+#[derive(Debug, FromRow)]
+struct CachedLookup {
+    results: Json<Vec<GeocodingResult>>,
+    cached_at: DateTime<Utc>,
+}
 
 pub struct GeocodingService;
 
 impl GeocodingService {
-    /// Lookup location data from city name using OpenWeatherMap Geocoding API
-    pub async fn lookup_city(city_name: &str, api_key: &str) -> Result<GeocodingResult> {
+    /// Lookup candidate locations for a city name with the configured geocoder, best match first.
+    ///
+    /// Upstream results are cached per normalised query; the bundled dataset is not.
+    pub async fn lookup_city(
+        pool: &PgPool,
+        http: &reqwest::Client,
+        city_name: &str,
+        limit: usize,
+        config: &ServerConfig,
+    ) -> Result<GeocodingLookup> {
+        match (config.geocoder, config.weather_api_key.as_deref()) {
+            (GeocoderKind::Offline, _) | (GeocoderKind::Auto, None) => Ok(GeocodingLookup {
+                results: Self::lookup_city_offline(city_name, limit),
+                cached_at: None,
+            }),
+            (GeocoderKind::OpenWeather | GeocoderKind::Auto, Some(api_key)) => {
+                let query = cache_key(city_name);
+                if let Some(cached) =
+                    Self::cached_lookup(pool, OPENWEATHER_PROVIDER, &query, limit).await?
+                {
+                    return Ok(GeocodingLookup {
+                        results: cached.results.0,
+                        cached_at: Some(cached.cached_at),
+                    });
+                }
+
+                let results =
+                    Self::lookup_city_openweather(http, config, api_key, city_name, limit).await?;
+                let ttl = Duration::hours(config.geocoding_cache_ttl_hours);
+                Self::cache_lookup(pool, OPENWEATHER_PROVIDER, &query, limit, &results, ttl)
+                    .await?;
+
+                Ok(GeocodingLookup {
+                    results,
+                    cached_at: None,
+                })
+            }
+            (GeocoderKind::OpenWeather, None) => {
+                anyhow::bail!("Weather API key not configured")
+            }
+        }
+    }
+
+    /// Lookup candidate locations in the bundled city dataset
+    pub fn lookup_city_offline(city_name: &str, limit: usize) -> Vec<GeocodingResult> {
+        gazetteer::search(city_name, limit)
+            .into_iter()
+            .map(Self::city_result)
+            .collect()
+    }
+
+    /// Lookup candidate locations using OpenWeatherMap Geocoding API
+    pub async fn lookup_city_openweather(
+        http: &reqwest::Client,
+        config: &ServerConfig,
+        api_key: &str,
+        city_name: &str,
+        limit: usize,
+    ) -> Result<Vec<GeocodingResult>> {
         let url = format!(
-            "http://api.openweathermap.org/geo/1.0/direct?q={}&limit=1&appid={}",
+            "{}/geo/1.0/direct?q={}&limit={}&appid={}",
+            config.openweather_base_url,
             urlencoding::encode(city_name),
+            limit.clamp(1, OPENWEATHER_MAX_RESULTS),
             api_key
         );
 
-        let client = reqwest::Client::new();
-        let response = client
-            .get(&url)
+        let geo_data = Self::fetch_openweather(http, &url).await?;
+
+        let mut results: Vec<GeocodingResult> = Vec::new();
+        for geo in &geo_data {
+            // OpenWeather sometimes lists the same place more than once
+            let duplicate = results.iter().any(|result| {
+                result.city_name == geo.name
+                    && result.country == geo.country
+                    && result.state == geo.state
+            });
+            if !duplicate {
+                results.push(Self::openweather_result(geo)?);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Find the place nearest to a point with the configured geocoder
+    pub async fn reverse(
+        http: &reqwest::Client,
+        coordinates: &Coordinates,
+        config: &ServerConfig,
+    ) -> Result<ReverseGeocodingResult> {
+        let place = match (config.geocoder, config.weather_api_key.as_deref()) {
+            (GeocoderKind::Offline, _) | (GeocoderKind::Auto, None) => {
+                gazetteer::nearest(coordinates).map(|(city, _)| Self::city_result(city))
+            }
+            (GeocoderKind::OpenWeather | GeocoderKind::Auto, Some(api_key)) => {
+                let url = format!(
+                    "{}/geo/1.0/reverse?lat={}&lon={}&limit=1&appid={}",
+                    config.openweather_base_url,
+                    coordinates.latitude(),
+                    coordinates.longitude(),
+                    api_key
+                );
+                let geo_data = Self::fetch_openweather(http, &url).await?;
+                geo_data.first().map(Self::openweather_result).transpose()?
+            }
+            (GeocoderKind::OpenWeather, None) => {
+                anyhow::bail!("Weather API key not configured")
+            }
+        };
+
+        Ok(ReverseGeocodingResult {
+            coordinates: *coordinates,
+            timezone: Self::timezone_at(coordinates),
+            distance_km: place
+                .as_ref()
+                .map(|place| place.coordinates.distance_km(coordinates)),
+            place,
+        })
+    }
+
+    async fn fetch_openweather(
+        http: &reqwest::Client,
+        url: &str,
+    ) -> Result<Vec<OpenWeatherGeoResponse>> {
+        let response = http
+            .get(url)
             .send()
             .await
+            .map_err(|e| e.without_url())
             .context("Failed to fetch geocoding data")?;
 
         if !response.status().is_success() {
@@ -26,26 +163,121 @@ impl GeocodingService {
             anyhow::bail!("Geocoding API error: {} - {}", status, body);
         }
 
-        let geo_data: Vec<OpenWeatherGeoResponse> = response
+        response
             .json()
             .await
-            .context("Failed to parse geocoding data")?;
-
-        let geo = geo_data
-            .first()
-            .context(format!("City '{}' not found", city_name))?;
+            .map_err(|e| e.without_url())
+            .context("Failed to parse geocoding data")
+    }
 
-        // Determine timezone from coordinates using tzf-rs
-        let finder = tzf_rs::DefaultFinder::new();
-        let timezone = finder.get_tz_name(geo.lon, geo.lat);
+    fn openweather_result(geo: &OpenWeatherGeoResponse) -> Result<GeocodingResult> {
+        let coordinates = Coordinates::try_from(geo)?;
 
         Ok(GeocodingResult {
             city_name: geo.name.clone(),
             country: geo.country.clone(),
             state: geo.state.clone(),
-            latitude: geo.lat as f32,
-            longitude: geo.lon as f32,
-            timezone: timezone.to_string(),
+            timezone: Self::timezone_at(&coordinates),
+            coordinates,
         })
     }
+
+    fn city_result(city: &City) -> GeocodingResult {
+        GeocodingResult {
+            city_name: city.name.clone(),
+            country: city.country.clone(),
+            state: city.admin.clone(),
+            timezone: Self::timezone_at(&city.coordinates),
+            coordinates: city.coordinates,
+        }
+    }
+
+    /// Delete cached lookups (only the expired ones if `expired_only`), returning how many
+    pub async fn purge_cache(pool: &PgPool, expired_only: bool) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM geocoding_cache
+            WHERE NOT $1 OR expires_at <= NOW()
+            "#,
+        )
+        .bind(expired_only)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn cached_lookup(
+        pool: &PgPool,
+        provider: &str,
+        query: &str,
+        limit: usize,
+    ) -> Result<Option<CachedLookup>> {
+        let cached = sqlx::query_as::<_, CachedLookup>(
+            r#"
+            SELECT results, cached_at FROM geocoding_cache
+            WHERE provider = $1 AND query = $2 AND result_limit = $3 AND expires_at > NOW()
+            "#,
+        )
+        .bind(provider)
+        .bind(query)
+        .bind(limit as i32)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(cached)
+    }
+
+    async fn cache_lookup(
+        pool: &PgPool,
+        provider: &str,
+        query: &str,
+        limit: usize,
+        results: &[GeocodingResult],
+        ttl: Duration,
+    ) -> Result<()> {
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO geocoding_cache
+                (provider, query, result_limit, results, cached_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (provider, query, result_limit)
+            DO UPDATE SET results = $4, cached_at = $5, expires_at = $6
+            "#,
+        )
+        .bind(provider)
+        .bind(query)
+        .bind(limit as i32)
+        .bind(Json(results))
+        .bind(now)
+        .bind(now + ttl)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Determine the IANA timezone at the given coordinates using tzf-rs
+    pub fn timezone_at(coordinates: &Coordinates) -> String {
+        // Building the finder loads the timezone polygons, so do it once
+        static FINDER: OnceLock<tzf_rs::DefaultFinder> = OnceLock::new();
+
+        FINDER
+            .get_or_init(tzf_rs::DefaultFinder::new)
+            .get_tz_name(coordinates.longitude(), coordinates.latitude())
+            .to_string()
+    }
+}
+
+/// Cache key for a query: lowercase, single-spaced, with tidy comma separators
+fn cache_key(query: &str) -> String {
+    query
+        .split(',')
+        .map(|part| part.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(",")
+        .to_lowercase()
 }
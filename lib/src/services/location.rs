@@ -1,18 +1,27 @@
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use chrono_tz::Tz;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::models::location::{CreateLocationRequest, Location, LocationTimeResponse};
+use crate::models::location::{
+    ConvertTimeRequest, ConvertTimeResponse, ConvertedTime, Coordinates, CreateLocationRequest,
+    LocalSlot, Location, LocationTimeResponse, LocationWorkingHours, OverlapRequest,
+    OverlapResponse, OverlapSlot, TimeOptions, UpdateLocationRequest,
+};
+use crate::services::geocoding::GeocodingService;
+use crate::services::location_group::LocationGroupService;
+use crate::solar::SolarTimes;
+use crate::timezone::{self, TimeFormat};
 
 pub struct LocationService;
 
 impl LocationService {
     /// Create a new location
     pub async fn create(pool: &PgPool, req: CreateLocationRequest) -> Result<Location> {
-        // Validate timezone string
+        // Validate timezone string and coordinates
         let _: Tz = req.timezone.parse()?;
+        let coordinates = Coordinates::from_parts(req.latitude, req.longitude)?;
 
         let id = Uuid::new_v4();
         let now = Utc::now();
@@ -27,8 +36,8 @@ impl LocationService {
         .bind(id)
         .bind(&req.name)
         .bind(&req.timezone)
-        .bind(req.latitude)
-        .bind(req.longitude)
+        .bind(coordinates.map(|c| c.latitude()))
+        .bind(coordinates.map(|c| c.longitude()))
         .bind(now)
         .fetch_one(pool)
         .await?;
@@ -78,6 +87,72 @@ impl LocationService {
         Ok(locations)
     }
 
+    /// List all locations, or a group's locations in group order (`None` if there is no such group)
+    pub async fn list_filtered(
+        pool: &PgPool,
+        group: Option<&str>,
+    ) -> Result<Option<Vec<Location>>> {
+        match group {
+            Some(name) => LocationGroupService::locations_by_name(pool, name).await,
+            None => Ok(Some(Self::list(pool).await?)),
+        }
+    }
+
+    /// Partially update a location
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        req: UpdateLocationRequest,
+    ) -> Result<Option<Location>> {
+        let Some(existing) = Self::get_by_id(pool, id).await? else {
+            return Ok(None);
+        };
+
+        let name = req.name.unwrap_or(existing.name);
+        if name.trim().is_empty() {
+            anyhow::bail!("Location name cannot be empty");
+        }
+
+        let coordinates = Coordinates::from_parts(
+            req.latitude.or(existing.coordinates.map(|c| c.latitude())),
+            req.longitude.or(existing.coordinates.map(|c| c.longitude())),
+        )?;
+
+        let timezone = match (req.derive_timezone, req.timezone) {
+            (true, Some(_)) => {
+                anyhow::bail!("Give either a timezone or derive_timezone, not both")
+            }
+            (true, None) => {
+                let Some(coordinates) = coordinates else {
+                    anyhow::bail!("Cannot derive timezone for a location without coordinates");
+                };
+                GeocodingService::timezone_at(&coordinates)
+            }
+            (false, timezone) => timezone.unwrap_or(existing.timezone),
+        };
+
+        // Validate timezone string
+        let _: Tz = timezone.parse()?;
+
+        let location = sqlx::query_as::<_, Location>(
+            r#"
+            UPDATE locations
+            SET name = $1, timezone = $2, latitude = $3, longitude = $4
+            WHERE id = $5
+            RETURNING *
+            "#,
+        )
+        .bind(&name)
+        .bind(&timezone)
+        .bind(coordinates.map(|c| c.latitude()))
+        .bind(coordinates.map(|c| c.longitude()))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(location)
+    }
+
     /// Delete a location
     pub async fn delete(pool: &PgPool, id: Uuid) -> Result<Option<Location>> {
         let location = sqlx::query_as::<_, Location>(
@@ -94,46 +169,263 @@ impl LocationService {
         Ok(location)
     }
 
+    /// Get the default location for an API token
+    pub async fn get_default(pool: &PgPool, token_hash: &str) -> Result<Option<Location>> {
+        let location = sqlx::query_as::<_, Location>(
+            r#"
+            SELECT l.* FROM locations l
+            JOIN default_locations d ON d.location_id = l.id
+            WHERE d.token_hash = $1
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(location)
+    }
+
+    /// Set the default location for an API token
+    pub async fn set_default(
+        pool: &PgPool,
+        token_hash: &str,
+        location_id: Uuid,
+    ) -> Result<Option<Location>> {
+        let Some(location) = Self::get_by_id(pool, location_id).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO default_locations (token_hash, location_id, updated_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (token_hash)
+            DO UPDATE SET location_id = EXCLUDED.location_id, updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(token_hash)
+        .bind(location_id)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        Ok(Some(location))
+    }
+
+    /// Clear the default location for an API token
+    pub async fn clear_default(pool: &PgPool, token_hash: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM default_locations WHERE token_hash = $1
+            "#,
+        )
+        .bind(token_hash)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Get current time at a location
-    pub async fn get_time(pool: &PgPool, id: Uuid) -> Result<Option<LocationTimeResponse>> {
+    pub async fn get_time(
+        pool: &PgPool,
+        id: Uuid,
+        options: &TimeOptions,
+    ) -> Result<Option<LocationTimeResponse>> {
         let location = Self::get_by_id(pool, id).await?;
 
         let Some(location) = location else {
             return Ok(None);
         };
 
-        let timezone: Tz = location.timezone.parse()?;
+        Ok(Some(Self::time_response(location, Utc::now(), options)?))
+    }
+
+    /// Get times for all locations, or the locations in a group
+    pub async fn list_times(
+        pool: &PgPool,
+        group: Option<&str>,
+        options: &TimeOptions,
+    ) -> Result<Option<Vec<LocationTimeResponse>>> {
+        let Some(locations) = Self::list_filtered(pool, group).await? else {
+            return Ok(None);
+        };
         let now_utc = Utc::now();
+
+        let responses = locations
+            .into_iter()
+            .map(|location| Self::time_response(location, now_utc, options))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(responses))
+    }
+
+    fn time_response(
+        location: Location,
+        now_utc: DateTime<Utc>,
+        options: &TimeOptions,
+    ) -> Result<LocationTimeResponse> {
+        let timezone: Tz = location.timezone.parse()?;
         let now_local = now_utc.with_timezone(&timezone);
+        let utc_offset_seconds = timezone::utc_offset_seconds(&now_local);
 
-        let formatted_time = now_local.format("%Y-%m-%d %H:%M:%S %Z").to_string();
+        let offset_from_default_seconds = match &options.reference {
+            Some(reference) => {
+                let reference_tz: Tz = reference.timezone.parse()?;
+                let reference_now = now_utc.with_timezone(&reference_tz);
+                Some(utc_offset_seconds - timezone::utc_offset_seconds(&reference_now))
+            }
+            None => None,
+        };
 
-        Ok(Some(LocationTimeResponse {
-            location,
+        Ok(LocationTimeResponse {
             current_time: now_utc,
-            formatted_time,
-        }))
+            formatted_time: options.format.format(&now_local),
+            utc_offset: now_local.format("%:z").to_string(),
+            utc_offset_seconds,
+            is_dst: timezone::is_dst(&now_local),
+            offset_from_default_seconds,
+            solar: SolarTimes::for_location(&location, now_local.date_naive()),
+            location,
+        })
     }
 
-    /// Get times for all locations
-    pub async fn list_times(pool: &PgPool) -> Result<Vec<LocationTimeResponse>> {
-        let locations = Self::list(pool).await?;
-        let now_utc = Utc::now();
+    /// Find the slots on a day where the working hours of all given locations overlap
+    pub async fn overlap(pool: &PgPool, req: OverlapRequest) -> Result<OverlapResponse> {
+        let locations = if req.location_ids.is_empty() {
+            Self::list(pool).await?
+        } else {
+            let mut locations = Vec::new();
+            for id in &req.location_ids {
+                let location = Self::get_by_id(pool, *id)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Location '{}' not found", id))?;
+                locations.push(location);
+            }
+            locations
+        };
+
+        if locations.is_empty() {
+            anyhow::bail!("No locations to plan for");
+        }
+
+        let reference = match req.reference {
+            Some(id) => locations
+                .iter()
+                .find(|location| location.id == id)
+                .ok_or_else(|| anyhow::anyhow!("Reference location '{}' is not being planned", id))?,
+            None => &locations[0],
+        };
+        let reference_tz: Tz = reference.timezone.parse()?;
+        let reference_timezone = reference.timezone.clone();
+        let day = timezone::day_bounds(&reference_tz, req.date);
+
+        let mut planned = Vec::new();
+        let mut overlap = vec![day];
+
+        for location in locations {
+            let tz: Tz = location.timezone.parse()?;
+            let hours = req
+                .hours
+                .get(&location.id)
+                .copied()
+                .unwrap_or(req.default_hours);
+            let windows =
+                timezone::working_intervals(&tz, hours.start, hours.end, day.start, day.end);
+
+            overlap = timezone::intersect(&overlap, &windows);
+            planned.push((
+                tz,
+                LocationWorkingHours {
+                    location,
+                    hours,
+                    windows,
+                },
+            ));
+        }
+
+        let slots = overlap
+            .into_iter()
+            .map(|slot| OverlapSlot {
+                start: slot.start,
+                end: slot.end,
+                duration_minutes: slot.duration().num_minutes(),
+                local: planned
+                    .iter()
+                    .map(|(tz, planned)| LocalSlot {
+                        location_id: planned.location.id,
+                        name: planned.location.name.clone(),
+                        start: slot.start.with_timezone(tz).format("%Y-%m-%d %H:%M %Z").to_string(),
+                        end: slot.end.with_timezone(tz).format("%Y-%m-%d %H:%M %Z").to_string(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Ok(OverlapResponse {
+            date: req.date,
+            reference_timezone,
+            day_start: day.start,
+            day_end: day.end,
+            locations: planned.into_iter().map(|(_, planned)| planned).collect(),
+            slots,
+        })
+    }
+
+    /// Convert a wall-clock time in one location (or timezone) to other locations
+    pub async fn convert(
+        pool: &PgPool,
+        req: ConvertTimeRequest,
+        format: &TimeFormat,
+    ) -> Result<ConvertTimeResponse> {
+        let from_timezone = match Uuid::parse_str(&req.from) {
+            Ok(id) => {
+                Self::get_by_id(pool, id)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Location '{}' not found", id))?
+                    .timezone
+            }
+            Err(_) => req.from.clone(),
+        };
+        let from_tz: Tz = from_timezone
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Unknown timezone '{}'", from_timezone))?;
+
+        let resolution = timezone::classify_local(&from_tz, req.time);
+
+        let locations = if req.to.is_empty() {
+            Self::list(pool).await?
+        } else {
+            let mut locations = Vec::new();
+            for id in &req.to {
+                let location = Self::get_by_id(pool, *id)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Location '{}' not found", id))?;
+                locations.push(location);
+            }
+            locations
+        };
 
-        let mut responses = Vec::new();
+        let mut conversions = Vec::new();
 
         for location in locations {
-            let timezone: Tz = location.timezone.parse()?;
-            let now_local = now_utc.with_timezone(&timezone);
-            let formatted_time = now_local.format("%Y-%m-%d %H:%M:%S %Z").to_string();
+            let tz: Tz = location.timezone.parse()?;
+            let render = |instant: DateTime<Utc>| format.format(&instant.with_timezone(&tz));
 
-            responses.push(LocationTimeResponse {
+            conversions.push(ConvertedTime {
+                formatted_time: render(resolution.instant),
+                alternative_formatted_time: resolution.alternative.map(render),
                 location,
-                current_time: now_utc,
-                formatted_time,
             });
         }
 
-        Ok(responses)
+        Ok(ConvertTimeResponse {
+            time: req.time,
+            from_timezone,
+            status: resolution.status,
+            instant: resolution.instant,
+            alternative_instant: resolution.alternative,
+            conversions,
+        })
     }
 }
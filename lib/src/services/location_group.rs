@@ -0,0 +1,227 @@
+use anyhow::Result;
+use chrono::Utc;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::models::location::{
+    CreateLocationGroupRequest, Location, LocationGroup, LocationGroupResponse,
+};
+
+pub struct LocationGroupService;
+
+impl LocationGroupService {
+    /// Create a new location group with its initial members
+    pub async fn create(pool: &PgPool, req: CreateLocationGroupRequest) -> Result<LocationGroup> {
+        if req.name.trim().is_empty() {
+            anyhow::bail!("Group name cannot be empty");
+        }
+
+        let mut tx = pool.begin().await?;
+
+        let group = sqlx::query_as::<_, LocationGroup>(
+            r#"
+            INSERT INTO location_groups (id, name, created_at)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(&req.name)
+        .bind(Utc::now())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::insert_members(&mut tx, group.id, &req.location_ids).await?;
+        tx.commit().await?;
+
+        Ok(group)
+    }
+
+    /// List all groups with their locations
+    pub async fn list(pool: &PgPool) -> Result<Vec<LocationGroupResponse>> {
+        let groups = sqlx::query_as::<_, LocationGroup>(
+            r#"
+            SELECT * FROM location_groups
+            ORDER BY name ASC
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut responses = Vec::new();
+        for group in groups {
+            let locations = Self::locations(pool, group.id).await?;
+            responses.push(LocationGroupResponse { group, locations });
+        }
+
+        Ok(responses)
+    }
+
+    /// Get a group by name
+    pub async fn get_by_name(pool: &PgPool, name: &str) -> Result<Option<LocationGroup>> {
+        let group = sqlx::query_as::<_, LocationGroup>(
+            r#"
+            SELECT * FROM location_groups WHERE name = $1
+            "#,
+        )
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(group)
+    }
+
+    /// Get a group and its locations by name
+    pub async fn get_response(pool: &PgPool, name: &str) -> Result<Option<LocationGroupResponse>> {
+        let Some(group) = Self::get_by_name(pool, name).await? else {
+            return Ok(None);
+        };
+
+        let locations = Self::locations(pool, group.id).await?;
+        Ok(Some(LocationGroupResponse { group, locations }))
+    }
+
+    /// The locations in a group, in group order
+    pub async fn locations(pool: &PgPool, group_id: Uuid) -> Result<Vec<Location>> {
+        let locations = sqlx::query_as::<_, Location>(
+            r#"
+            SELECT l.* FROM locations l
+            JOIN location_group_members m ON m.location_id = l.id
+            WHERE m.group_id = $1
+            ORDER BY m.position ASC, l.name ASC
+            "#,
+        )
+        .bind(group_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(locations)
+    }
+
+    /// The locations in a group looked up by name (`None` if there is no such group)
+    pub async fn locations_by_name(pool: &PgPool, name: &str) -> Result<Option<Vec<Location>>> {
+        let Some(group) = Self::get_by_name(pool, name).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self::locations(pool, group.id).await?))
+    }
+
+    /// Replace a group's members, keeping the given order
+    pub async fn set_locations(
+        pool: &PgPool,
+        name: &str,
+        location_ids: &[Uuid],
+    ) -> Result<Option<LocationGroupResponse>> {
+        let Some(group) = Self::get_by_name(pool, name).await? else {
+            return Ok(None);
+        };
+
+        let mut tx = pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM location_group_members WHERE group_id = $1
+            "#,
+        )
+        .bind(group.id)
+        .execute(&mut *tx)
+        .await?;
+
+        Self::insert_members(&mut tx, group.id, location_ids).await?;
+        tx.commit().await?;
+
+        let locations = Self::locations(pool, group.id).await?;
+        Ok(Some(LocationGroupResponse { group, locations }))
+    }
+
+    /// Add a location to a group (or move it) at the given position, appending by default
+    pub async fn add_location(
+        pool: &PgPool,
+        name: &str,
+        location_id: Uuid,
+        position: Option<i32>,
+    ) -> Result<Option<LocationGroupResponse>> {
+        let Some(group) = Self::get_by_name(pool, name).await? else {
+            return Ok(None);
+        };
+
+        let mut ids: Vec<Uuid> = Self::locations(pool, group.id)
+            .await?
+            .into_iter()
+            .map(|location| location.id)
+            .filter(|id| *id != location_id)
+            .collect();
+
+        let index = position
+            .map(|position| (position.max(0) as usize).min(ids.len()))
+            .unwrap_or(ids.len());
+        ids.insert(index, location_id);
+
+        Self::set_locations(pool, name, &ids).await
+    }
+
+    /// Remove a location from a group
+    pub async fn remove_location(
+        pool: &PgPool,
+        name: &str,
+        location_id: Uuid,
+    ) -> Result<Option<LocationGroupResponse>> {
+        let Some(group) = Self::get_by_name(pool, name).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            DELETE FROM location_group_members
+            WHERE group_id = $1 AND location_id = $2
+            "#,
+        )
+        .bind(group.id)
+        .bind(location_id)
+        .execute(pool)
+        .await?;
+
+        let locations = Self::locations(pool, group.id).await?;
+        Ok(Some(LocationGroupResponse { group, locations }))
+    }
+
+    /// Delete a group (its locations are kept)
+    pub async fn delete(pool: &PgPool, name: &str) -> Result<Option<LocationGroup>> {
+        let group = sqlx::query_as::<_, LocationGroup>(
+            r#"
+            DELETE FROM location_groups
+            WHERE name = $1
+            RETURNING *
+            "#,
+        )
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(group)
+    }
+
+    async fn insert_members(
+        tx: &mut Transaction<'_, Postgres>,
+        group_id: Uuid,
+        location_ids: &[Uuid],
+    ) -> Result<()> {
+        for (position, location_id) in location_ids.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO location_group_members (group_id, location_id, position)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (group_id, location_id) DO NOTHING
+                "#,
+            )
+            .bind(group_id)
+            .bind(location_id)
+            .bind(position as i32)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
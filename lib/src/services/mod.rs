@@ -5,5 +5,9 @@ pub mod location;
 pub mod task;
 pub mod template;
 pub mod database;
+pub mod database_server;
+pub mod location_group;
 pub mod weather;
+pub mod weather_alert;
 pub mod geocoding;
+pub mod team;
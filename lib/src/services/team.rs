@@ -0,0 +1,213 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use chrono_tz::Tz;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::team::{
+    CreateTeamMemberRequest, TeamMember, TeamMemberTimeResponse, UpdateTeamMemberRequest,
+};
+use crate::services::location::LocationService;
+use crate::timezone::{self, TimeFormat};
+
+/// Team members joined with their location, resolving the effective timezone
+const SELECT_MEMBERS: &str = r#"
+    SELECT m.id, m.name, m.email, m.location_id, l.name AS location_name,
+           COALESCE(l.timezone, m.timezone) AS timezone,
+           m.work_start, m.work_end, m.created_at
+    FROM team_members m
+    LEFT JOIN locations l ON l.id = m.location_id
+"#;
+
+pub struct TeamService;
+
+impl TeamService {
+    /// Add a team member
+    pub async fn create(pool: &PgPool, req: CreateTeamMemberRequest) -> Result<TeamMember> {
+        if req.name.trim().is_empty() {
+            anyhow::bail!("Team member name cannot be empty");
+        }
+
+        let timezone = match (req.location_id, req.timezone) {
+            (Some(location_id), None) => Self::location_timezone(pool, location_id).await?,
+            (None, Some(timezone)) => {
+                let _: Tz = timezone.parse()?;
+                timezone
+            }
+            _ => anyhow::bail!("Give either a location or a timezone for a team member"),
+        };
+
+        let id = Uuid::new_v4();
+
+        sqlx::query(
+            r#"
+            INSERT INTO team_members
+                (id, name, email, location_id, timezone, work_start, work_end, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(id)
+        .bind(&req.name)
+        .bind(&req.email)
+        .bind(req.location_id)
+        .bind(&timezone)
+        .bind(req.work_start.unwrap_or(default_start()))
+        .bind(req.work_end.unwrap_or(default_end()))
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        Self::get_by_id(pool, id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Team member '{}' disappeared after insert", id))
+    }
+
+    /// Get a team member by ID
+    pub async fn get_by_id(pool: &PgPool, id: Uuid) -> Result<Option<TeamMember>> {
+        let member =
+            sqlx::query_as::<_, TeamMember>(&format!("{} WHERE m.id = $1", SELECT_MEMBERS))
+                .bind(id)
+                .fetch_optional(pool)
+                .await?;
+
+        Ok(member)
+    }
+
+    /// List all team members
+    pub async fn list(pool: &PgPool) -> Result<Vec<TeamMember>> {
+        let members =
+            sqlx::query_as::<_, TeamMember>(&format!("{} ORDER BY m.name ASC", SELECT_MEMBERS))
+                .fetch_all(pool)
+                .await?;
+
+        Ok(members)
+    }
+
+    /// Partially update a team member
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        req: UpdateTeamMemberRequest,
+    ) -> Result<Option<TeamMember>> {
+        let Some(existing) = Self::get_by_id(pool, id).await? else {
+            return Ok(None);
+        };
+
+        let name = req.name.unwrap_or(existing.name);
+        if name.trim().is_empty() {
+            anyhow::bail!("Team member name cannot be empty");
+        }
+
+        let (location_id, timezone) = match (req.location_id, req.timezone) {
+            (Some(_), Some(_)) => {
+                anyhow::bail!("Give either a location or a timezone for a team member")
+            }
+            (Some(location_id), None) => (
+                Some(location_id),
+                Self::location_timezone(pool, location_id).await?,
+            ),
+            (None, Some(timezone)) => {
+                let _: Tz = timezone.parse()?;
+                (None, timezone)
+            }
+            (None, None) => (existing.location_id, existing.timezone),
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE team_members
+            SET name = $1, email = $2, location_id = $3, timezone = $4,
+                work_start = $5, work_end = $6
+            WHERE id = $7
+            "#,
+        )
+        .bind(&name)
+        .bind(req.email.or(existing.email))
+        .bind(location_id)
+        .bind(&timezone)
+        .bind(req.work_start.unwrap_or(existing.work_start))
+        .bind(req.work_end.unwrap_or(existing.work_end))
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Remove a team member
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<Option<TeamMember>> {
+        let Some(member) = Self::get_by_id(pool, id).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            DELETE FROM team_members WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(Some(member))
+    }
+
+    /// Current local time of every team member and whether they are working
+    pub async fn now(pool: &PgPool, format: &TimeFormat) -> Result<Vec<TeamMemberTimeResponse>> {
+        let now = Utc::now();
+
+        Self::list(pool)
+            .await?
+            .into_iter()
+            .map(|member| Self::time_response(member, now, format))
+            .collect()
+    }
+
+    fn time_response(
+        member: TeamMember,
+        now_utc: DateTime<Utc>,
+        format: &TimeFormat,
+    ) -> Result<TeamMemberTimeResponse> {
+        let timezone: Tz = member.timezone.parse()?;
+        let now_local = now_utc.with_timezone(&timezone);
+
+        // Looking two days ahead always reaches the next start or end of the working day
+        let windows = timezone::working_intervals(
+            &timezone,
+            member.work_start,
+            member.work_end,
+            now_utc,
+            now_utc + Duration::days(2),
+        );
+        let (within_working_hours, next_change) = match windows.first() {
+            Some(window) if window.start <= now_utc => (true, Some(window.end)),
+            Some(window) => (false, Some(window.start)),
+            None => (false, None),
+        };
+
+        Ok(TeamMemberTimeResponse {
+            current_time: now_utc,
+            formatted_time: format.format(&now_local),
+            utc_offset: now_local.format("%:z").to_string(),
+            within_working_hours,
+            next_change,
+            member,
+        })
+    }
+
+    async fn location_timezone(pool: &PgPool, location_id: Uuid) -> Result<String> {
+        let location = LocationService::get_by_id(pool, location_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Location '{}' not found", location_id))?;
+
+        Ok(location.timezone)
+    }
+}
+
+fn default_start() -> NaiveTime {
+    NaiveTime::from_hms_opt(9, 0, 0).expect("valid time")
+}
+
+fn default_end() -> NaiveTime {
+    NaiveTime::from_hms_opt(17, 0, 0).expect("valid time")
+}
@@ -1,10 +1,25 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use sqlx::PgPool;
+use tokio::task::JoinSet;
 use uuid::Uuid;
 
 use crate::models::location::Location;
-use crate::models::weather::{OpenWeatherResponse, WeatherResponse};
+use crate::models::weather::{
+    AirQualityResponse, DailyForecastEntry, DailyForecastResponse, HistoryInterval,
+    HourlyForecastEntry, HourlyForecastResponse, WeatherHistoryPoint, WeatherHistoryResponse,
+    WeatherResponse,
+};
 use crate::services::location::LocationService;
+use crate::weather::air::{AqiCategory, UvRisk};
+use crate::weather::units::UnitSystem;
+use crate::weather::WeatherCache;
+
+/// Hours covered by the hourly forecast
+pub const FORECAST_HOURS: usize = 48;
+/// Days covered by the daily forecast
+pub const FORECAST_DAYS: usize = 7;
 
 pub struct WeatherService;
 
@@ -13,95 +28,336 @@ impl WeatherService {
     pub async fn get_for_location(
         pool: &PgPool,
         location_id: Uuid,
-        api_key: &str,
+        cache: &WeatherCache,
+        units: UnitSystem,
     ) -> Result<WeatherResponse> {
         let location = LocationService::get_by_id(pool, location_id)
             .await?
             .context("Location not found")?;
 
-        Self::fetch_weather(&location, api_key).await
+        Self::fetch_weather(&location, cache, units).await
     }
 
-    /// Get weather for all locations
+    /// Get weather for all locations, or a group's locations (`None` if there is no such group).
+    ///
+    /// Locations are fetched concurrently (the cache bounds upstream requests) and returned
+    /// in list order.
     pub async fn get_for_all_locations(
         pool: &PgPool,
-        api_key: &str,
-    ) -> Result<Vec<WeatherResponse>> {
-        let locations = LocationService::list(pool).await?;
-        let mut weather_responses = Vec::new();
+        group: Option<&str>,
+        cache: &WeatherCache,
+        units: UnitSystem,
+    ) -> Result<Option<Vec<WeatherResponse>>> {
+        let Some(locations) = LocationService::list_filtered(pool, group).await? else {
+            return Ok(None);
+        };
+
+        let mut fetches = JoinSet::new();
+        for (index, location) in locations.into_iter().enumerate() {
+            if location.coordinates.is_some() {
+                let cache = cache.clone();
+                fetches.spawn(async move {
+                    let weather = Self::fetch_weather(&location, &cache, units).await;
+                    (index, location.name, weather)
+                });
+            }
+        }
 
-        for location in locations {
-            if location.latitude.is_some() && location.longitude.is_some() {
-                match Self::fetch_weather(&location, api_key).await {
-                    Ok(weather) => weather_responses.push(weather),
-                    Err(e) => {
-                        tracing::warn!(
-                            "Failed to fetch weather for location {}: {}",
-                            location.name,
-                            e
-                        );
-                    }
+        let mut weather_responses = Vec::new();
+        while let Some(fetched) = fetches.join_next().await {
+            let (index, name, weather) = fetched?;
+            match weather {
+                Ok(weather) => weather_responses.push((index, weather)),
+                Err(e) => {
+                    tracing::warn!("Failed to fetch weather for location {}: {}", name, e);
                 }
             }
         }
+        weather_responses.sort_by_key(|(index, _)| *index);
 
-        Ok(weather_responses)
+        Ok(Some(
+            weather_responses
+                .into_iter()
+                .map(|(_, weather)| weather)
+                .collect(),
+        ))
     }
 
-    /// Fetch weather from OpenWeatherMap API
-    async fn fetch_weather(location: &Location, api_key: &str) -> Result<WeatherResponse> {
-        let (lat, lon) = match (location.latitude, location.longitude) {
-            (Some(lat), Some(lon)) => (lat, lon),
-            _ => anyhow::bail!("Location does not have coordinates"),
+    /// Hourly forecast for the next 48 hours (`None` if the location does not exist)
+    pub async fn hourly_forecast(
+        pool: &PgPool,
+        location_id: Uuid,
+        cache: &WeatherCache,
+        units: UnitSystem,
+    ) -> Result<Option<HourlyForecastResponse>> {
+        let Some(location) = LocationService::get_by_id(pool, location_id).await? else {
+            return Ok(None);
         };
+        let coordinates = location
+            .coordinates
+            .context("Location does not have coordinates")?;
+        let tz: Tz = location.timezone.parse()?;
+
+        let hours = cache
+            .hourly(&coordinates, FORECAST_HOURS)
+            .await?
+            .into_iter()
+            .map(|hour| HourlyForecastEntry {
+                time: hour.time.with_timezone(&tz).fixed_offset(),
+                temperature_celsius: hour.temperature_celsius,
+                temperature_fahrenheit: fahrenheit(hour.temperature_celsius),
+                temperature: units.temperature(hour.temperature_celsius),
+                precipitation_probability: hour.precipitation_probability,
+                description: hour.description,
+            })
+            .collect();
 
-        let url = format!(
-            "https://api.openweathermap.org/data/2.5/weather?lat={}&lon={}&appid={}&units=metric",
-            lat, lon, api_key
-        );
-
-        let client = reqwest::Client::new();
-        let response = client
-            .get(&url)
-            .send()
-            .await
-            .context("Failed to fetch weather data")?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Weather API error: {} - {}", status, body);
+        Ok(Some(HourlyForecastResponse {
+            location_id: location.id,
+            location_name: location.name,
+            units,
+            unit_labels: units.labels(),
+            hours,
+        }))
+    }
+
+    /// Daily forecast for the next 7 days (`None` if the location does not exist)
+    pub async fn daily_forecast(
+        pool: &PgPool,
+        location_id: Uuid,
+        cache: &WeatherCache,
+        units: UnitSystem,
+    ) -> Result<Option<DailyForecastResponse>> {
+        let Some(location) = LocationService::get_by_id(pool, location_id).await? else {
+            return Ok(None);
+        };
+        let coordinates = location
+            .coordinates
+            .context("Location does not have coordinates")?;
+        let tz: Tz = location.timezone.parse()?;
+
+        let days = cache
+            .daily(&coordinates, tz, FORECAST_DAYS)
+            .await?
+            .into_iter()
+            .map(|day| DailyForecastEntry {
+                date: day.date,
+                temperature_min_celsius: day.temperature_min_celsius,
+                temperature_max_celsius: day.temperature_max_celsius,
+                temperature_min_fahrenheit: fahrenheit(day.temperature_min_celsius),
+                temperature_max_fahrenheit: fahrenheit(day.temperature_max_celsius),
+                temperature_min: units.temperature(day.temperature_min_celsius),
+                temperature_max: units.temperature(day.temperature_max_celsius),
+                precipitation_probability: day.precipitation_probability,
+                description: day.description,
+            })
+            .collect();
+
+        Ok(Some(DailyForecastResponse {
+            location_id: location.id,
+            location_name: location.name,
+            units,
+            unit_labels: units.labels(),
+            days,
+        }))
+    }
+
+    /// Current air quality and UV index (`None` if the location does not exist)
+    pub async fn air_quality(
+        pool: &PgPool,
+        location_id: Uuid,
+        cache: &WeatherCache,
+    ) -> Result<Option<AirQualityResponse>> {
+        let Some(location) = LocationService::get_by_id(pool, location_id).await? else {
+            return Ok(None);
+        };
+        let coordinates = location
+            .coordinates
+            .context("Location does not have coordinates")?;
+
+        let air = cache.air_quality(&coordinates).await?;
+
+        Ok(Some(AirQualityResponse {
+            location_id: location.id,
+            location_name: location.name,
+            category: AqiCategory::for_readings(&air),
+            pm2_5: air.pm2_5,
+            pm10: air.pm10,
+            ozone: air.ozone,
+            nitrogen_dioxide: air.nitrogen_dioxide,
+            uv_index: air.uv_index,
+            uv_risk: air.uv_index.map(UvRisk::for_index),
+        }))
+    }
+
+    /// Snapshot current weather for every location with coordinates, returning how many
+    /// new observations were stored.
+    ///
+    /// Each location gets a new reading from the provider (which also refreshes the cache),
+    /// stamped with the time it was fetched.
+    pub async fn record_observations(pool: &PgPool, cache: &WeatherCache) -> Result<u64> {
+        let mut fetches = JoinSet::new();
+        for location in LocationService::list(pool).await? {
+            if let Some(coordinates) = location.coordinates {
+                let cache = cache.clone();
+                fetches.spawn(async move {
+                    let reading = cache.refresh(&coordinates).await;
+                    (location, reading)
+                });
+            }
+        }
+
+        let mut recorded = 0;
+        while let Some(fetched) = fetches.join_next().await {
+            let (location, reading) = fetched?;
+            let reading = match reading {
+                Ok(reading) => reading,
+                Err(e) => {
+                    tracing::warn!("Failed to record weather for {}: {}", location.name, e);
+                    continue;
+                }
+            };
+            let weather = reading.weather;
+
+            let result = sqlx::query(
+                r#"
+                INSERT INTO weather_observations
+                    (location_id, observed_at, temperature_celsius, feels_like_celsius,
+                     humidity, description, wind_speed_ms)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (location_id, observed_at) DO NOTHING
+                "#,
+            )
+            .bind(location.id)
+            .bind(reading.cached_at)
+            .bind(weather.temperature_celsius)
+            .bind(weather.feels_like_celsius)
+            .bind(weather.humidity as i16)
+            .bind(&weather.description)
+            .bind(weather.wind_speed_ms)
+            .execute(pool)
+            .await?;
+
+            recorded += result.rows_affected();
+        }
+
+        Ok(recorded)
+    }
+
+    /// Delete observations older than `cutoff`, returning how many were removed
+    pub async fn purge_observations(pool: &PgPool, cutoff: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM weather_observations WHERE observed_at < $1")
+            .bind(cutoff)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Recorded weather between `from` and `to`, aggregated per hour or per local day
+    /// (`None` if the location does not exist)
+    pub async fn history(
+        pool: &PgPool,
+        location_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        interval: HistoryInterval,
+        units: UnitSystem,
+    ) -> Result<Option<WeatherHistoryResponse>> {
+        if from >= to {
+            anyhow::bail!("'from' must be before 'to'");
+        }
+
+        let Some(location) = LocationService::get_by_id(pool, location_id).await? else {
+            return Ok(None);
+        };
+
+        let mut points = sqlx::query_as::<_, WeatherHistoryPoint>(
+            r#"
+            SELECT
+                date_trunc($1, observed_at AT TIME ZONE $2) AT TIME ZONE $2 AS period_start,
+                MIN(temperature_celsius) AS temperature_min,
+                AVG(temperature_celsius)::REAL AS temperature_avg,
+                MAX(temperature_celsius) AS temperature_max,
+                AVG(humidity)::REAL AS humidity_avg,
+                MAX(wind_speed_ms) AS wind_speed_max,
+                COUNT(*) AS observations
+            FROM weather_observations
+            WHERE location_id = $3 AND observed_at >= $4 AND observed_at < $5
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+        )
+        .bind(interval.date_trunc_unit())
+        .bind(&location.timezone)
+        .bind(location_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(pool)
+        .await?;
+
+        // Observations are stored in metric units
+        for point in &mut points {
+            point.temperature_min = units.temperature(point.temperature_min);
+            point.temperature_avg = units.temperature(point.temperature_avg);
+            point.temperature_max = units.temperature(point.temperature_max);
+            point.wind_speed_max = units.speed(point.wind_speed_max);
         }
 
-        let weather_data: OpenWeatherResponse = response
-            .json()
-            .await
-            .context("Failed to parse weather data")?;
+        Ok(Some(WeatherHistoryResponse {
+            location_id: location.id,
+            location_name: location.name,
+            interval,
+            from,
+            to,
+            units,
+            unit_labels: units.labels(),
+            points,
+        }))
+    }
 
-        let description = weather_data
-            .weather
-            .first()
-            .map(|w| w.description.clone())
-            .unwrap_or_else(|| "Unknown".to_string());
+    /// Current weather for a location, from the cache or the provider
+    async fn fetch_weather(
+        location: &Location,
+        cache: &WeatherCache,
+        units: UnitSystem,
+    ) -> Result<WeatherResponse> {
+        let coordinates = location
+            .coordinates
+            .context("Location does not have coordinates")?;
+        let tz: Tz = location.timezone.parse()?;
+
+        let cached = cache.current(&coordinates).await?;
+        let weather = cached.weather;
+        let local = |time: DateTime<Utc>| time.with_timezone(&tz).fixed_offset();
 
         Ok(WeatherResponse {
             location_id: location.id,
             location_name: location.name.clone(),
-            temperature_celsius: weather_data.main.temp,
-            temperature_fahrenheit: celsius_to_fahrenheit(weather_data.main.temp),
-            feels_like_celsius: weather_data.main.feels_like,
-            humidity: weather_data.main.humidity,
-            description,
-            wind_speed_ms: weather_data.wind.speed,
-            wind_speed_mph: ms_to_mph(weather_data.wind.speed),
+            temperature_celsius: weather.temperature_celsius,
+            temperature_fahrenheit: fahrenheit(weather.temperature_celsius),
+            feels_like_celsius: weather.feels_like_celsius,
+            humidity: weather.humidity,
+            description: weather.description,
+            wind_speed_ms: weather.wind_speed_ms,
+            wind_speed_mph: UnitSystem::Imperial.speed(weather.wind_speed_ms),
+            units,
+            unit_labels: units.labels(),
+            temperature: units.temperature(weather.temperature_celsius),
+            feels_like: units.temperature(weather.feels_like_celsius),
+            wind_speed: units.speed(weather.wind_speed_ms),
+            wind_direction_degrees: weather.wind_direction_degrees,
+            pressure: weather.pressure_hpa.map(|hpa| units.pressure(hpa)),
+            visibility: weather.visibility_m.map(|metres| units.distance(metres)),
+            cloud_cover: weather.cloud_cover,
+            sunrise: weather.sunrise.map(local),
+            sunset: weather.sunset.map(local),
+            cached_at: cached.cached_at,
+            stale: cached.stale,
         })
     }
 }
 
-fn celsius_to_fahrenheit(celsius: f32) -> f32 {
-    (celsius * 9.0 / 5.0) + 32.0
-}
-
-fn ms_to_mph(ms: f32) -> f32 {
-    ms * 2.237
+fn fahrenheit(celsius: f32) -> f32 {
+    UnitSystem::Imperial.temperature(celsius)
 }
@@ -0,0 +1,248 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::models::weather_alert::{
+    AlertStatus, CreateWeatherRuleRequest, WeatherAlert, WeatherRule, WeatherRuleKind,
+};
+use crate::services::location::LocationService;
+use crate::weather::notify::NotificationHook;
+use crate::weather::rules::{self, MAX_RAIN_WINDOW_HOURS};
+use crate::weather::WeatherCache;
+
+const SELECT_RULES: &str = r#"
+    SELECT r.id, r.location_id, l.name AS location_name, r.kind, r.threshold, r.created_at
+    FROM weather_rules r
+    JOIN locations l ON l.id = r.location_id
+"#;
+
+const SELECT_ALERTS: &str = r#"
+    SELECT a.id, a.rule_id, r.location_id, l.name AS location_name, r.kind, r.threshold,
+        CASE WHEN a.resolved_at IS NULL THEN 'firing' ELSE 'resolved' END AS status,
+        a.value, a.message, a.fired_at, a.resolved_at
+    FROM weather_alerts a
+    JOIN weather_rules r ON r.id = a.rule_id
+    JOIN locations l ON l.id = r.location_id
+"#;
+
+pub struct WeatherAlertService;
+
+impl WeatherAlertService {
+    /// Create a rule for a location with coordinates
+    pub async fn create_rule(pool: &PgPool, req: CreateWeatherRuleRequest) -> Result<WeatherRule> {
+        rules::validate_threshold(req.kind, req.threshold)?;
+
+        let location = LocationService::get_by_id(pool, req.location_id)
+            .await?
+            .context("Location not found")?;
+        if location.coordinates.is_none() {
+            anyhow::bail!("Location '{}' has no coordinates", location.name);
+        }
+
+        let id = Uuid::new_v4();
+
+        sqlx::query(
+            r#"
+            INSERT INTO weather_rules (id, location_id, kind, threshold, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(id)
+        .bind(req.location_id)
+        .bind(req.kind)
+        .bind(req.threshold)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        Self::get_rule(pool, id)
+            .await?
+            .context("Weather rule missing after insert")
+    }
+
+    pub async fn get_rule(pool: &PgPool, id: Uuid) -> Result<Option<WeatherRule>> {
+        let rule = sqlx::query_as::<_, WeatherRule>(&format!("{} WHERE r.id = $1", SELECT_RULES))
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(rule)
+    }
+
+    /// List rules, optionally for one location
+    pub async fn list_rules(pool: &PgPool, location_id: Option<Uuid>) -> Result<Vec<WeatherRule>> {
+        let rules = sqlx::query_as::<_, WeatherRule>(&format!(
+            "{} WHERE $1::UUID IS NULL OR r.location_id = $1 ORDER BY l.name, r.created_at",
+            SELECT_RULES
+        ))
+        .bind(location_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    /// Delete a rule and its alerts, returning the deleted rule
+    pub async fn delete_rule(pool: &PgPool, id: Uuid) -> Result<Option<WeatherRule>> {
+        let Some(rule) = Self::get_rule(pool, id).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query("DELETE FROM weather_rules WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Ok(Some(rule))
+    }
+
+    /// List alerts, most recent first
+    pub async fn list_alerts(
+        pool: &PgPool,
+        status: Option<AlertStatus>,
+        limit: i64,
+    ) -> Result<Vec<WeatherAlert>> {
+        let resolved = status.map(|status| status == AlertStatus::Resolved);
+        let alerts = sqlx::query_as::<_, WeatherAlert>(&format!(
+            "{} WHERE $1::BOOLEAN IS NULL OR (a.resolved_at IS NOT NULL) = $1 \
+             ORDER BY a.fired_at DESC LIMIT $2",
+            SELECT_ALERTS
+        ))
+        .bind(resolved)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(alerts)
+    }
+
+    async fn get_alert(pool: &PgPool, id: Uuid) -> Result<WeatherAlert> {
+        let alert =
+            sqlx::query_as::<_, WeatherAlert>(&format!("{} WHERE a.id = $1", SELECT_ALERTS))
+                .bind(id)
+                .fetch_one(pool)
+                .await?;
+
+        Ok(alert)
+    }
+
+    /// Check every rule against fresh weather, opening and resolving alerts as rules start and
+    /// stop firing. Each transition is passed to `hook`; the changed alerts are returned.
+    ///
+    /// A stale cached reading is replaced with a new one from the provider before evaluating.
+    /// Failures for one location or rule are logged and skipped so the rest are still checked.
+    pub async fn evaluate(
+        pool: &PgPool,
+        cache: &WeatherCache,
+        hook: &impl NotificationHook,
+    ) -> Result<Vec<WeatherAlert>> {
+        let mut by_location: BTreeMap<Uuid, Vec<WeatherRule>> = BTreeMap::new();
+        for rule in Self::list_rules(pool, None).await? {
+            by_location.entry(rule.location_id).or_default().push(rule);
+        }
+
+        let mut changed = Vec::new();
+        for (location_id, location_rules) in by_location {
+            let coordinates = match LocationService::get_by_id(pool, location_id).await {
+                Ok(location) => location.and_then(|location| location.coordinates),
+                Err(e) => {
+                    tracing::warn!("Skipping weather rules for {}: {:?}", location_id, e);
+                    continue;
+                }
+            };
+            let Some(coordinates) = coordinates else {
+                continue;
+            };
+
+            let current = match cache.fresh(&coordinates).await {
+                Ok(current) => current.weather,
+                Err(e) => {
+                    tracing::warn!("Skipping weather rules for {}: {}", location_id, e);
+                    continue;
+                }
+            };
+
+            let needs_forecast = location_rules
+                .iter()
+                .any(|rule| rule.kind == WeatherRuleKind::RainWithin);
+            let hourly = if needs_forecast {
+                let hours = MAX_RAIN_WINDOW_HOURS as usize;
+                match cache.hourly(&coordinates, hours).await {
+                    Ok(hourly) => hourly,
+                    Err(e) => {
+                        tracing::warn!("Skipping weather rules for {}: {}", location_id, e);
+                        continue;
+                    }
+                }
+            } else {
+                vec![]
+            };
+
+            let now = Utc::now();
+            for rule in location_rules {
+                let outcome = rules::evaluate(rule.kind, rule.threshold, &current, &hourly, now);
+                let alert = match Self::transition(pool, &rule, &outcome).await {
+                    Ok(Some(alert)) => alert,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        tracing::warn!("Failed to update alerts for rule {}: {:?}", rule.id, e);
+                        continue;
+                    }
+                };
+                if let Err(e) = hook.notify(&alert).await {
+                    tracing::error!("Failed to deliver weather alert {}: {:?}", alert.id, e);
+                }
+                changed.push(alert);
+            }
+        }
+
+        Ok(changed)
+    }
+
+    /// Open or resolve the rule's alert if its firing state changed
+    async fn transition(
+        pool: &PgPool,
+        rule: &WeatherRule,
+        outcome: &rules::RuleOutcome,
+    ) -> Result<Option<WeatherAlert>> {
+        let open: Option<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM weather_alerts WHERE rule_id = $1 AND resolved_at IS NULL",
+        )
+        .bind(rule.id)
+        .fetch_optional(pool)
+        .await?;
+
+        let id = match (outcome.firing, open) {
+            (true, None) => {
+                let id = Uuid::new_v4();
+                sqlx::query(
+                    r#"
+                    INSERT INTO weather_alerts (id, rule_id, value, message, fired_at)
+                    VALUES ($1, $2, $3, $4, $5)
+                    "#,
+                )
+                .bind(id)
+                .bind(rule.id)
+                .bind(outcome.value)
+                .bind(&outcome.message)
+                .bind(Utc::now())
+                .execute(pool)
+                .await?;
+                id
+            }
+            (false, Some(id)) => {
+                sqlx::query("UPDATE weather_alerts SET resolved_at = $2 WHERE id = $1")
+                    .bind(id)
+                    .bind(Utc::now())
+                    .execute(pool)
+                    .await?;
+                id
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(Self::get_alert(pool, id).await?))
+    }
+}
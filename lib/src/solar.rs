@@ -0,0 +1,185 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::location::Location;
+
+/// Julian day of the J2000.0 epoch (2000-01-01 12:00 UTC)
+const J2000: f64 = 2_451_545.0;
+/// Julian day of the Unix epoch
+const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+/// Axial tilt of the Earth, in degrees
+const OBLIQUITY: f64 = 23.4397;
+/// Sun altitude at sunrise/sunset, accounting for refraction and the solar disc
+const SUNRISE_ALTITUDE: f64 = -0.833;
+/// Sun altitude at the start/end of civil twilight
+const CIVIL_TWILIGHT_ALTITUDE: f64 = -6.0;
+
+/// Sun events for a place on a given day, in UTC.
+///
+/// Events are `None` when they do not happen that day: during polar day the
+/// sun never sets, during polar night it never rises.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SolarTimes {
+    pub civil_dawn: Option<DateTime<Utc>>,
+    pub sunrise: Option<DateTime<Utc>>,
+    pub solar_noon: DateTime<Utc>,
+    pub sunset: Option<DateTime<Utc>>,
+    pub civil_dusk: Option<DateTime<Utc>>,
+    /// Time between sunrise and sunset (a full day during polar day)
+    pub day_length_seconds: i64,
+}
+
+impl SolarTimes {
+    /// Compute the sun events for `date` at the given coordinates (degrees, east positive)
+    pub fn calculate(latitude: f64, longitude: f64, date: NaiveDate) -> SolarTimes {
+        let epoch = NaiveDate::from_ymd_opt(2000, 1, 1).expect("valid date");
+        let n = (date - epoch).num_days() as f64 + 0.0008;
+
+        // Mean solar time at the location
+        let mean_solar = n - longitude / 360.0;
+
+        let anomaly = (357.5291 + 0.985_600_28 * mean_solar).rem_euclid(360.0);
+        let anomaly_rad = anomaly.to_radians();
+        let center = 1.9148 * anomaly_rad.sin()
+            + 0.0200 * (2.0 * anomaly_rad).sin()
+            + 0.0003 * (3.0 * anomaly_rad).sin();
+        let ecliptic_longitude = (anomaly + center + 180.0 + 102.9372).rem_euclid(360.0);
+        let ecliptic_rad = ecliptic_longitude.to_radians();
+
+        let transit =
+            J2000 + mean_solar + 0.0053 * anomaly_rad.sin() - 0.0069 * (2.0 * ecliptic_rad).sin();
+        let declination = (ecliptic_rad.sin() * OBLIQUITY.to_radians().sin()).asin();
+
+        let events = |altitude: f64| {
+            hour_angle(latitude, declination, altitude).map(|angle| {
+                (
+                    julian_to_utc(transit - angle / 360.0),
+                    julian_to_utc(transit + angle / 360.0),
+                )
+            })
+        };
+
+        let sunrise = events(SUNRISE_ALTITUDE);
+        let civil = events(CIVIL_TWILIGHT_ALTITUDE);
+
+        let day_length_seconds = match sunrise {
+            HourAngle::Crosses((rise, set)) => (set - rise).num_seconds(),
+            HourAngle::AlwaysAbove => 86_400,
+            HourAngle::AlwaysBelow => 0,
+        };
+
+        SolarTimes {
+            civil_dawn: civil.rise(),
+            sunrise: sunrise.rise(),
+            solar_noon: julian_to_utc(transit),
+            sunset: sunrise.set(),
+            civil_dusk: civil.set(),
+            day_length_seconds,
+        }
+    }
+
+    /// Sun events at a location on its local `date` (`None` without coordinates)
+    pub fn for_location(location: &Location, date: NaiveDate) -> Option<SolarTimes> {
+        let coordinates = location.coordinates?;
+        Some(Self::calculate(
+            coordinates.latitude(),
+            coordinates.longitude(),
+            date,
+        ))
+    }
+}
+
+/// Whether the sun crosses a given altitude during the day
+#[derive(Debug, Clone, Copy)]
+enum HourAngle<T> {
+    Crosses(T),
+    AlwaysAbove,
+    AlwaysBelow,
+}
+
+impl<T> HourAngle<T> {
+    fn map<U>(self, f: impl FnOnce(T) -> U) -> HourAngle<U> {
+        match self {
+            HourAngle::Crosses(value) => HourAngle::Crosses(f(value)),
+            HourAngle::AlwaysAbove => HourAngle::AlwaysAbove,
+            HourAngle::AlwaysBelow => HourAngle::AlwaysBelow,
+        }
+    }
+}
+
+impl HourAngle<(DateTime<Utc>, DateTime<Utc>)> {
+    fn rise(self) -> Option<DateTime<Utc>> {
+        match self {
+            HourAngle::Crosses((rise, _)) => Some(rise),
+            _ => None,
+        }
+    }
+
+    fn set(self) -> Option<DateTime<Utc>> {
+        match self {
+            HourAngle::Crosses((_, set)) => Some(set),
+            _ => None,
+        }
+    }
+}
+
+/// Hour angle (degrees) at which the sun reaches `altitude`
+fn hour_angle(latitude: f64, declination: f64, altitude: f64) -> HourAngle<f64> {
+    let latitude = latitude.to_radians();
+    let cos_angle = (altitude.to_radians().sin() - latitude.sin() * declination.sin())
+        / (latitude.cos() * declination.cos());
+
+    if cos_angle > 1.0 {
+        HourAngle::AlwaysBelow
+    } else if cos_angle < -1.0 {
+        HourAngle::AlwaysAbove
+    } else {
+        HourAngle::Crosses(cos_angle.acos().to_degrees())
+    }
+}
+
+fn julian_to_utc(julian_day: f64) -> DateTime<Utc> {
+    let millis = ((julian_day - UNIX_EPOCH_JD) * 86_400_000.0).round() as i64;
+    DateTime::from_timestamp_millis(millis).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Option<DateTime<Utc>>, expected: &str) {
+        let expected: DateTime<Utc> = expected.parse().unwrap();
+        let actual = actual.expect("event should happen");
+        let diff = (actual - expected).num_seconds().abs();
+        assert!(diff <= 120, "expected {expected}, got {actual}");
+    }
+
+    #[test]
+    fn berlin_midsummer() {
+        let date = NaiveDate::from_ymd_opt(2026, 6, 21).unwrap();
+        let times = SolarTimes::calculate(52.52, 13.405, date);
+
+        assert_close(times.sunrise, "2026-06-21T02:43:00Z");
+        assert_close(times.sunset, "2026-06-21T19:33:00Z");
+        assert_close(times.civil_dawn, "2026-06-21T01:52:00Z");
+        assert_close(times.civil_dusk, "2026-06-21T20:24:00Z");
+        assert!((times.day_length_seconds - (16 * 3600 + 50 * 60)).abs() <= 180);
+    }
+
+    #[test]
+    fn polar_day_and_night() {
+        // Tromsø, Norway
+        let summer =
+            SolarTimes::calculate(69.65, 18.96, NaiveDate::from_ymd_opt(2026, 6, 21).unwrap());
+        assert_eq!(summer.sunrise, None);
+        assert_eq!(summer.sunset, None);
+        assert_eq!(summer.day_length_seconds, 86_400);
+
+        let winter =
+            SolarTimes::calculate(69.65, 18.96, NaiveDate::from_ymd_opt(2026, 12, 21).unwrap());
+        assert_eq!(winter.sunrise, None);
+        assert_eq!(winter.day_length_seconds, 0);
+        // The sun still gets close enough to the horizon for civil twilight
+        assert!(winter.civil_dawn.is_some());
+    }
+}
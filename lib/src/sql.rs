@@ -0,0 +1,183 @@
+/// Split a SQL script into individual statements.
+///
+/// Statements are separated by `;`. Semicolons inside string literals,
+/// quoted identifiers, dollar-quoted bodies and comments are ignored.
+/// Statements consisting only of whitespace and comments are dropped.
+pub fn split_statements(sql: &str) -> Vec<String> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut has_content = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        match c {
+            // Line comment
+            '-' if next == Some('-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    current.push(chars[i]);
+                    i += 1;
+                }
+                continue;
+            }
+            // Block comment (Postgres allows nesting)
+            '/' if next == Some('*') => {
+                let mut depth = 0;
+                while i < chars.len() {
+                    if chars[i] == '/' && chars.get(i + 1) == Some(&'*') {
+                        depth += 1;
+                        current.push_str("/*");
+                        i += 2;
+                    } else if chars[i] == '*' && chars.get(i + 1) == Some(&'/') {
+                        depth -= 1;
+                        current.push_str("*/");
+                        i += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else {
+                        current.push(chars[i]);
+                        i += 1;
+                    }
+                }
+                continue;
+            }
+            // String literal or quoted identifier
+            '\'' | '"' => {
+                let escapes = c == '\''
+                    && i > 0
+                    && matches!(chars[i - 1], 'E' | 'e')
+                    && (i < 2 || !chars[i - 2].is_alphanumeric());
+                has_content = true;
+                current.push(c);
+                i += 1;
+                while i < chars.len() {
+                    let ch = chars[i];
+                    current.push(ch);
+                    i += 1;
+                    if escapes && ch == '\\' {
+                        if let Some(&escaped) = chars.get(i) {
+                            current.push(escaped);
+                            i += 1;
+                        }
+                    } else if ch == c {
+                        // A doubled quote is an escaped quote
+                        if chars.get(i) == Some(&c) {
+                            current.push(c);
+                            i += 1;
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                continue;
+            }
+            // Dollar-quoted string ($$ ... $$ or $tag$ ... $tag$)
+            '$' => {
+                if let Some(tag) = dollar_tag(&chars[i..]) {
+                    has_content = true;
+                    current.push_str(&tag);
+                    i += tag.chars().count();
+                    let tag_chars: Vec<char> = tag.chars().collect();
+                    while i < chars.len() {
+                        if chars[i..].starts_with(&tag_chars) {
+                            current.push_str(&tag);
+                            i += tag_chars.len();
+                            break;
+                        }
+                        current.push(chars[i]);
+                        i += 1;
+                    }
+                    continue;
+                }
+            }
+            ';' => {
+                if has_content {
+                    statements.push(current.trim().to_string());
+                }
+                current.clear();
+                has_content = false;
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        if !c.is_whitespace() {
+            has_content = true;
+        }
+        current.push(c);
+        i += 1;
+    }
+
+    if has_content {
+        statements.push(current.trim().to_string());
+    }
+
+    statements
+}
+
+/// Return the opening dollar-quote tag (e.g. `$$` or `$body$`) at the start of `chars`, if any
+fn dollar_tag(chars: &[char]) -> Option<String> {
+    let mut tag = String::from("$");
+
+    for (idx, &c) in chars.iter().enumerate().skip(1) {
+        if c == '$' {
+            tag.push('$');
+            return Some(tag);
+        }
+
+        let valid = if idx == 1 {
+            c.is_alphabetic() || c == '_'
+        } else {
+            c.is_alphanumeric() || c == '_'
+        };
+
+        if !valid {
+            return None;
+        }
+        tag.push(c);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_simple_statements() {
+        let statements = split_statements("CREATE TABLE a (id INT);\nINSERT INTO a VALUES (1);");
+        assert_eq!(
+            statements,
+            vec!["CREATE TABLE a (id INT)", "INSERT INTO a VALUES (1)"]
+        );
+    }
+
+    #[test]
+    fn ignores_semicolons_in_strings_and_comments() {
+        let sql = "INSERT INTO a VALUES ('x;y', 'it''s');\n-- comment; here\nSELECT \"weird;name\" FROM a /* ; */;";
+        let statements = split_statements(sql);
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0], "INSERT INTO a VALUES ('x;y', 'it''s')");
+        assert!(statements[1].ends_with("FROM a /* ; */"));
+    }
+
+    #[test]
+    fn handles_dollar_quoted_bodies() {
+        let sql = "CREATE FUNCTION f() RETURNS void AS $body$ BEGIN PERFORM 1; END; $body$ LANGUAGE plpgsql;\nSELECT 1;";
+        let statements = split_statements(sql);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].contains("PERFORM 1; END;"));
+    }
+
+    #[test]
+    fn drops_empty_and_comment_only_statements() {
+        let statements = split_statements("-- header\n;;\nSELECT 1;\n-- trailing comment\n");
+        assert_eq!(statements, vec!["SELECT 1"]);
+    }
+}
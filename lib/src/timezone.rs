@@ -0,0 +1,344 @@
+use anyhow::Result;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{
+    DateTime, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc,
+};
+use chrono_tz::{OffsetComponents, Tz};
+use serde::Serialize;
+use std::str::FromStr;
+
+/// A half-open `[start, end)` span of time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Interval {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl Interval {
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+}
+
+/// How to render a local time
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum TimeFormat {
+    /// `2026-11-03 09:00:00 CET`
+    #[default]
+    TwentyFourHour,
+    /// `2026-11-03 09:00:00 AM CET`
+    TwelveHour,
+    /// `2026-11-03T09:00:00+01:00`
+    Rfc3339,
+    /// A strftime pattern
+    Custom(String),
+}
+
+impl FromStr for TimeFormat {
+    type Err = anyhow::Error;
+
+    /// Parse a preset name (`24h`, `12h`, `rfc3339`) or a strftime pattern
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "" | "24h" => Ok(TimeFormat::TwentyFourHour),
+            "12h" => Ok(TimeFormat::TwelveHour),
+            "rfc3339" => Ok(TimeFormat::Rfc3339),
+            _ => {
+                // Formatting with an invalid pattern fails at render time, so reject it up front
+                if StrftimeItems::new(value).any(|item| matches!(item, Item::Error)) {
+                    anyhow::bail!("Invalid time format '{}'", value);
+                }
+                Ok(TimeFormat::Custom(value.to_string()))
+            }
+        }
+    }
+}
+
+impl TimeFormat {
+    pub fn format(&self, time: &DateTime<Tz>) -> String {
+        match self {
+            TimeFormat::TwentyFourHour => time.format("%Y-%m-%d %H:%M:%S %Z").to_string(),
+            TimeFormat::TwelveHour => time.format("%Y-%m-%d %I:%M:%S %p %Z").to_string(),
+            TimeFormat::Rfc3339 => time.to_rfc3339(),
+            TimeFormat::Custom(pattern) => time.format(pattern).to_string(),
+        }
+    }
+}
+
+/// Offset from UTC in seconds
+pub fn utc_offset_seconds(time: &DateTime<Tz>) -> i32 {
+    time.offset().fix().local_minus_utc()
+}
+
+/// Whether daylight saving time is in effect
+pub fn is_dst(time: &DateTime<Tz>) -> bool {
+    !time.offset().dst_offset().is_zero()
+}
+
+/// Parse a time of day written as `HH:MM` or `HH:MM:SS`
+pub fn parse_time_of_day(value: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M:%S"))
+        .map_err(|_| anyhow::anyhow!("Invalid time '{}', expected HH:MM", value))
+}
+
+/// How a wall-clock time maps onto real instants in a timezone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LocalTimeStatus {
+    /// The time occurs exactly once
+    Unique,
+    /// The time occurs twice because clocks fall back
+    Ambiguous,
+    /// The time is skipped because clocks spring forward
+    Nonexistent,
+}
+
+/// A wall-clock time resolved to an instant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalResolution {
+    pub status: LocalTimeStatus,
+    pub instant: DateTime<Utc>,
+    /// The second occurrence of an ambiguous time
+    pub alternative: Option<DateTime<Utc>>,
+}
+
+/// Parse a local date and time such as `2026-11-03 09:00` or `2026-11-03T09:00:30`
+pub fn parse_local_datetime(value: &str) -> Result<NaiveDateTime> {
+    [
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value.trim(), format).ok())
+    .ok_or_else(|| anyhow::anyhow!("Invalid time '{}', expected YYYY-MM-DD HH:MM", value))
+}
+
+/// Resolve a wall-clock time in `tz`, reporting whether it is ambiguous or nonexistent.
+///
+/// Ambiguous times (when clocks fall back) resolve to the earlier instant.
+/// Times inside a gap (when clocks spring forward) are shifted forward by the
+/// length of the gap, so 02:30 on a 02:00 → 03:00 transition becomes 03:30.
+pub fn classify_local(tz: &Tz, local: NaiveDateTime) -> LocalResolution {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(dt) => LocalResolution {
+            status: LocalTimeStatus::Unique,
+            instant: dt.with_timezone(&Utc),
+            alternative: None,
+        },
+        LocalResult::Ambiguous(earlier, later) => LocalResolution {
+            status: LocalTimeStatus::Ambiguous,
+            instant: earlier.with_timezone(&Utc),
+            alternative: Some(later.with_timezone(&Utc)),
+        },
+        LocalResult::None => {
+            // Interpret the time with the offset in effect just before the gap
+            let offset = (1..=24)
+                .find_map(|hours| {
+                    tz.from_local_datetime(&(local - Duration::hours(hours)))
+                        .earliest()
+                })
+                .map(|before| before.offset().fix().local_minus_utc())
+                .unwrap_or(0);
+
+            LocalResolution {
+                status: LocalTimeStatus::Nonexistent,
+                instant: DateTime::from_naive_utc_and_offset(
+                    local - Duration::seconds(offset as i64),
+                    Utc,
+                ),
+                alternative: None,
+            }
+        }
+    }
+}
+
+/// Resolve a wall-clock time in `tz` to an instant (see [`classify_local`])
+pub fn resolve_local(tz: &Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    classify_local(tz, local).instant
+}
+
+/// Working-hour intervals in `tz` that fall within `[from, to)`.
+///
+/// A window whose end is not after its start runs past midnight into the
+/// next local day. Each local day's window is resolved separately, so the
+/// intervals follow any DST transition in the range.
+pub fn working_intervals(
+    tz: &Tz,
+    start: NaiveTime,
+    end: NaiveTime,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<Interval> {
+    let first_day = from.with_timezone(tz).date_naive() - Duration::days(1);
+    let last_day = to.with_timezone(tz).date_naive();
+
+    let mut intervals = Vec::new();
+    let mut day = first_day;
+
+    while day <= last_day {
+        let end_day = if end <= start {
+            day + Duration::days(1)
+        } else {
+            day
+        };
+        let window_start = resolve_local(tz, day.and_time(start)).max(from);
+        let window_end = resolve_local(tz, end_day.and_time(end)).min(to);
+
+        if window_start < window_end {
+            intervals.push(Interval {
+                start: window_start,
+                end: window_end,
+            });
+        }
+
+        day += Duration::days(1);
+    }
+
+    intervals
+}
+
+/// Intersect two sorted lists of non-overlapping intervals
+pub fn intersect(a: &[Interval], b: &[Interval]) -> Vec<Interval> {
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        let start = a[i].start.max(b[j].start);
+        let end = a[i].end.min(b[j].end);
+
+        if start < end {
+            result.push(Interval { start, end });
+        }
+
+        if a[i].end < b[j].end {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    result
+}
+
+/// The instants at which `date` starts and ends in `tz`
+pub fn day_bounds(tz: &Tz, date: NaiveDate) -> Interval {
+    Interval {
+        start: resolve_local(tz, date.and_time(NaiveTime::MIN)),
+        end: resolve_local(tz, (date + Duration::days(1)).and_time(NaiveTime::MIN)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn resolves_dst_gaps_and_overlaps() {
+        let berlin: Tz = "Europe/Berlin".parse().unwrap();
+
+        // Spring forward: 02:30 does not exist and is shifted to 03:30 CEST
+        let gap = NaiveDate::from_ymd_opt(2026, 3, 29)
+            .unwrap()
+            .and_hms_opt(2, 30, 0)
+            .unwrap();
+        assert_eq!(resolve_local(&berlin, gap), utc("2026-03-29T01:30:00Z"));
+
+        // Fall back: 02:30 happens twice, the earlier (CEST) one wins
+        let overlap = NaiveDate::from_ymd_opt(2026, 10, 25)
+            .unwrap()
+            .and_hms_opt(2, 30, 0)
+            .unwrap();
+        assert_eq!(resolve_local(&berlin, overlap), utc("2026-10-25T00:30:00Z"));
+    }
+
+    #[test]
+    fn formats_times() {
+        let berlin: Tz = "Europe/Berlin".parse().unwrap();
+        let time = utc("2026-07-01T13:05:00Z").with_timezone(&berlin);
+
+        assert_eq!(
+            TimeFormat::default().format(&time),
+            "2026-07-01 15:05:00 CEST"
+        );
+        assert_eq!(
+            "12h".parse::<TimeFormat>().unwrap().format(&time),
+            "2026-07-01 03:05:00 PM CEST"
+        );
+        assert_eq!(
+            "rfc3339".parse::<TimeFormat>().unwrap().format(&time),
+            "2026-07-01T15:05:00+02:00"
+        );
+        assert_eq!(
+            "%H:%M".parse::<TimeFormat>().unwrap().format(&time),
+            "15:05"
+        );
+        assert!("%Q".parse::<TimeFormat>().is_err());
+
+        assert_eq!(utc_offset_seconds(&time), 7200);
+        assert!(is_dst(&time));
+    }
+
+    #[test]
+    fn classifies_local_times() {
+        let new_york: Tz = "America/New_York".parse().unwrap();
+
+        let normal = classify_local(&new_york, parse_local_datetime("2026-11-03 09:00").unwrap());
+        assert_eq!(normal.status, LocalTimeStatus::Unique);
+        assert_eq!(normal.instant, utc("2026-11-03T14:00:00Z"));
+
+        let repeated = classify_local(&new_york, parse_local_datetime("2026-11-01T01:30").unwrap());
+        assert_eq!(repeated.status, LocalTimeStatus::Ambiguous);
+        assert_eq!(repeated.alternative, Some(utc("2026-11-01T06:30:00Z")));
+
+        let skipped = classify_local(&new_york, parse_local_datetime("2026-03-08 02:15").unwrap());
+        assert_eq!(skipped.status, LocalTimeStatus::Nonexistent);
+        assert_eq!(skipped.instant, utc("2026-03-08T07:15:00Z"));
+    }
+
+    #[test]
+    fn intersects_working_hours_across_timezones() {
+        let berlin: Tz = "Europe/Berlin".parse().unwrap();
+        let new_york: Tz = "America/New_York".parse().unwrap();
+        let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        let five = NaiveTime::from_hms_opt(17, 0, 0).unwrap();
+
+        // Between the European and US DST changes the gap is only 5 hours
+        let day = day_bounds(&berlin, NaiveDate::from_ymd_opt(2026, 10, 27).unwrap());
+        let overlap = intersect(
+            &working_intervals(&berlin, nine, five, day.start, day.end),
+            &working_intervals(&new_york, nine, five, day.start, day.end),
+        );
+
+        assert_eq!(
+            overlap,
+            vec![Interval {
+                start: utc("2026-10-27T13:00:00Z"),
+                end: utc("2026-10-27T16:00:00Z"),
+            }]
+        );
+    }
+
+    #[test]
+    fn windows_can_span_midnight() {
+        let tokyo: Tz = "Asia/Tokyo".parse().unwrap();
+        let day = day_bounds(&tokyo, NaiveDate::from_ymd_opt(2026, 5, 1).unwrap());
+        let intervals = working_intervals(
+            &tokyo,
+            NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+            day.start,
+            day.end,
+        );
+
+        assert_eq!(intervals.len(), 2);
+        assert_eq!(intervals[0].duration(), Duration::hours(6));
+        assert_eq!(intervals[1].duration(), Duration::hours(2));
+    }
+}
@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::AirQuality;
+
+/// Upper bounds (µg/m³) of the European AQI bands for PM2.5, PM10, NO2 and O3, from good to
+/// very poor; anything above the last bound is extremely poor
+const PM2_5_BANDS: [f32; 5] = [10.0, 20.0, 25.0, 50.0, 75.0];
+const PM10_BANDS: [f32; 5] = [20.0, 40.0, 50.0, 100.0, 150.0];
+const NO2_BANDS: [f32; 5] = [40.0, 90.0, 120.0, 230.0, 340.0];
+const O3_BANDS: [f32; 5] = [50.0, 100.0, 130.0, 240.0, 380.0];
+
+/// European Air Quality Index category, set by the worst of the measured pollutants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AqiCategory {
+    Good,
+    Fair,
+    Moderate,
+    Poor,
+    VeryPoor,
+    ExtremelyPoor,
+}
+
+impl AqiCategory {
+    const ALL: [AqiCategory; 6] = [
+        AqiCategory::Good,
+        AqiCategory::Fair,
+        AqiCategory::Moderate,
+        AqiCategory::Poor,
+        AqiCategory::VeryPoor,
+        AqiCategory::ExtremelyPoor,
+    ];
+
+    pub fn for_readings(air: &AirQuality) -> Self {
+        [
+            band(air.pm2_5, &PM2_5_BANDS),
+            band(air.pm10, &PM10_BANDS),
+            band(air.nitrogen_dioxide, &NO2_BANDS),
+            band(air.ozone, &O3_BANDS),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(AqiCategory::Good)
+    }
+}
+
+impl fmt::Display for AqiCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AqiCategory::Good => write!(f, "good"),
+            AqiCategory::Fair => write!(f, "fair"),
+            AqiCategory::Moderate => write!(f, "moderate"),
+            AqiCategory::Poor => write!(f, "poor"),
+            AqiCategory::VeryPoor => write!(f, "very poor"),
+            AqiCategory::ExtremelyPoor => write!(f, "extremely poor"),
+        }
+    }
+}
+
+fn band(value: f32, bounds: &[f32; 5]) -> AqiCategory {
+    let index = bounds.iter().take_while(|&&bound| value > bound).count();
+    AqiCategory::ALL[index]
+}
+
+/// WHO exposure category for a UV index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UvRisk {
+    Low,
+    Moderate,
+    High,
+    VeryHigh,
+    Extreme,
+}
+
+impl UvRisk {
+    pub fn for_index(uv_index: f32) -> Self {
+        // The index is reported to one decimal but the bands are for whole numbers
+        match uv_index.round() {
+            index if index < 3.0 => UvRisk::Low,
+            index if index < 6.0 => UvRisk::Moderate,
+            index if index < 8.0 => UvRisk::High,
+            index if index < 11.0 => UvRisk::VeryHigh,
+            _ => UvRisk::Extreme,
+        }
+    }
+}
+
+impl fmt::Display for UvRisk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UvRisk::Low => write!(f, "low"),
+            UvRisk::Moderate => write!(f, "moderate"),
+            UvRisk::High => write!(f, "high"),
+            UvRisk::VeryHigh => write!(f, "very high"),
+            UvRisk::Extreme => write!(f, "extreme"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn air(pm2_5: f32, pm10: f32, nitrogen_dioxide: f32, ozone: f32) -> AirQuality {
+        AirQuality {
+            pm2_5,
+            pm10,
+            ozone,
+            nitrogen_dioxide,
+            uv_index: None,
+        }
+    }
+
+    #[test]
+    fn worst_pollutant_sets_the_category() {
+        assert_eq!(
+            AqiCategory::for_readings(&air(4.0, 9.0, 12.0, 40.0)),
+            AqiCategory::Good
+        );
+        // Bounds belong to the lower band
+        assert_eq!(
+            AqiCategory::for_readings(&air(10.0, 20.0, 40.0, 50.0)),
+            AqiCategory::Good
+        );
+        assert_eq!(
+            AqiCategory::for_readings(&air(4.0, 9.0, 95.0, 40.0)),
+            AqiCategory::Moderate
+        );
+        // Wildfire smoke
+        assert_eq!(
+            AqiCategory::for_readings(&air(180.0, 210.0, 30.0, 60.0)),
+            AqiCategory::ExtremelyPoor
+        );
+    }
+
+    #[test]
+    fn uv_risk_bands() {
+        assert_eq!(UvRisk::for_index(0.0), UvRisk::Low);
+        assert_eq!(UvRisk::for_index(2.4), UvRisk::Low);
+        assert_eq!(UvRisk::for_index(2.6), UvRisk::Moderate);
+        assert_eq!(UvRisk::for_index(7.0), UvRisk::High);
+        assert_eq!(UvRisk::for_index(10.4), UvRisk::VeryHigh);
+        assert_eq!(UvRisk::for_index(11.0), UvRisk::Extreme);
+    }
+}
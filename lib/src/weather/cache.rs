@@ -0,0 +1,465 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
+
+use super::{
+    AirQuality, CurrentWeather, DailyForecast, HourlyForecast, WeatherClient, WeatherProvider,
+};
+use crate::config::ServerConfig;
+use crate::location::Coordinates;
+
+/// Current weather as served from the cache
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedWeather {
+    pub weather: CurrentWeather,
+    /// When the provider returned this reading
+    pub cached_at: DateTime<Utc>,
+    /// Older than the freshness window (a refresh has been started or has failed)
+    pub stale: bool,
+}
+
+/// Cache key: the exact coordinates of a location
+type CacheKey = (u64, u64);
+
+/// In-memory cache of current conditions per location, in front of a weather provider.
+///
+/// Readings are fresh for `fresh_for`. After that they are still served (marked stale)
+/// for up to `stale_for` while a background refresh replaces them; older readings are
+/// fetched again before answering, falling back to the old reading if that fails.
+///
+/// Forecasts and air quality are cached for `fresh_for` and then fetched again. At most
+/// `concurrency` upstream requests of any kind run at a time.
+#[derive(Debug, Clone)]
+pub struct WeatherCache<P = WeatherClient> {
+    inner: Arc<Inner<P>>,
+}
+
+#[derive(Debug)]
+struct Inner<P> {
+    provider: P,
+    entries: Mutex<HashMap<CacheKey, (CurrentWeather, DateTime<Utc>)>>,
+    refreshing: Mutex<HashSet<CacheKey>>,
+    hourly: Expiring<(CacheKey, usize), Vec<HourlyForecast>>,
+    daily: Expiring<(CacheKey, Tz, usize), Vec<DailyForecast>>,
+    air_quality: Expiring<CacheKey, AirQuality>,
+    fetch_limit: Semaphore,
+    fresh_for: Duration,
+    stale_for: Duration,
+}
+
+/// Values kept for a fixed time, with no stale serving
+#[derive(Debug)]
+struct Expiring<K, V> {
+    entries: Mutex<HashMap<K, (V, DateTime<Utc>)>>,
+}
+
+impl<K: Eq + Hash, V: Clone> Expiring<K, V> {
+    fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, key: &K, max_age: Duration) -> Option<V> {
+        let entries = self.entries.lock().unwrap();
+        let (value, cached_at) = entries.get(key)?;
+        (Utc::now() - *cached_at < max_age).then(|| value.clone())
+    }
+
+    /// Store a value, dropping any that have expired
+    fn insert(&self, key: K, value: V, max_age: Duration) {
+        let now = Utc::now();
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (_, cached_at)| now - *cached_at < max_age);
+        entries.insert(key, (value, now));
+    }
+}
+
+impl WeatherCache<WeatherClient> {
+    pub fn from_config(config: &ServerConfig, provider: WeatherClient) -> Self {
+        Self::new(
+            provider,
+            Duration::seconds(config.weather_cache_ttl_secs),
+            Duration::seconds(config.weather_cache_max_stale_secs),
+            config.weather_fetch_concurrency,
+        )
+    }
+}
+
+impl<P> WeatherCache<P>
+where
+    P: WeatherProvider + Send + Sync + 'static,
+{
+    pub fn new(provider: P, fresh_for: Duration, stale_for: Duration, concurrency: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                provider,
+                entries: Mutex::new(HashMap::new()),
+                refreshing: Mutex::new(HashSet::new()),
+                hourly: Expiring::new(),
+                daily: Expiring::new(),
+                air_quality: Expiring::new(),
+                fetch_limit: Semaphore::new(concurrency.max(1)),
+                fresh_for,
+                stale_for,
+            }),
+        }
+    }
+
+    /// The provider behind the cache
+    pub fn provider(&self) -> &P {
+        &self.inner.provider
+    }
+
+    /// Current conditions at the given coordinates, from the cache when possible
+    pub async fn current(&self, coordinates: &Coordinates) -> Result<CachedWeather> {
+        let key = cache_key(coordinates);
+        let cached = self.inner.entries.lock().unwrap().get(&key).cloned();
+        let now = Utc::now();
+
+        if let Some((weather, cached_at)) = &cached {
+            let age = now - *cached_at;
+            if age < self.inner.fresh_for {
+                return Ok(CachedWeather {
+                    weather: weather.clone(),
+                    cached_at: *cached_at,
+                    stale: false,
+                });
+            }
+            if age < self.inner.fresh_for + self.inner.stale_for {
+                self.refresh_in_background(key, *coordinates);
+                return Ok(CachedWeather {
+                    weather: weather.clone(),
+                    cached_at: *cached_at,
+                    stale: true,
+                });
+            }
+        }
+
+        match self.inner.fetch(key, coordinates).await {
+            Ok((weather, cached_at)) => Ok(CachedWeather {
+                weather,
+                cached_at,
+                stale: false,
+            }),
+            Err(e) => match cached {
+                Some((weather, cached_at)) => {
+                    tracing::warn!("Serving stale weather after a failed refresh: {}", e);
+                    Ok(CachedWeather {
+                        weather,
+                        cached_at,
+                        stale: true,
+                    })
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Current conditions no older than the freshness window, fetching a new reading instead
+    /// of serving a stale one
+    pub async fn fresh(&self, coordinates: &Coordinates) -> Result<CachedWeather> {
+        let cached = self
+            .inner
+            .entries
+            .lock()
+            .unwrap()
+            .get(&cache_key(coordinates))
+            .cloned();
+
+        match cached {
+            Some((weather, cached_at)) if Utc::now() - cached_at < self.inner.fresh_for => {
+                Ok(CachedWeather {
+                    weather,
+                    cached_at,
+                    stale: false,
+                })
+            }
+            _ => self.refresh(coordinates).await,
+        }
+    }
+
+    /// Fetch a new reading from the provider regardless of the cached one, and cache it
+    pub async fn refresh(&self, coordinates: &Coordinates) -> Result<CachedWeather> {
+        let (weather, cached_at) = self
+            .inner
+            .fetch(cache_key(coordinates), coordinates)
+            .await?;
+
+        Ok(CachedWeather {
+            weather,
+            cached_at,
+            stale: false,
+        })
+    }
+
+    /// Forecast for (up to) the next `hours` hours
+    pub async fn hourly(
+        &self,
+        coordinates: &Coordinates,
+        hours: usize,
+    ) -> Result<Vec<HourlyForecast>> {
+        let key = (cache_key(coordinates), hours);
+        let fetch = self.inner.provider.hourly(coordinates, hours);
+        self.inner.cached(&self.inner.hourly, key, fetch).await
+    }
+
+    /// Forecast for (up to) `days` days starting today, with days in `timezone`
+    pub async fn daily(
+        &self,
+        coordinates: &Coordinates,
+        timezone: Tz,
+        days: usize,
+    ) -> Result<Vec<DailyForecast>> {
+        let key = (cache_key(coordinates), timezone, days);
+        let fetch = self.inner.provider.daily(coordinates, timezone, days);
+        self.inner.cached(&self.inner.daily, key, fetch).await
+    }
+
+    /// Current air quality (and UV index, if available)
+    pub async fn air_quality(&self, coordinates: &Coordinates) -> Result<AirQuality> {
+        let key = cache_key(coordinates);
+        let fetch = self.inner.provider.air_quality(coordinates);
+        self.inner.cached(&self.inner.air_quality, key, fetch).await
+    }
+
+    /// Start refreshing an entry unless a refresh for it is already running
+    fn refresh_in_background(&self, key: CacheKey, coordinates: Coordinates) {
+        if !self.inner.refreshing.lock().unwrap().insert(key) {
+            return;
+        }
+
+        let inner = self.inner.clone();
+        tokio::spawn(async move {
+            if let Err(e) = inner.fetch(key, &coordinates).await {
+                tracing::warn!("Background weather refresh failed: {}", e);
+            }
+            inner.refreshing.lock().unwrap().remove(&key);
+        });
+    }
+}
+
+impl<P: WeatherProvider> Inner<P> {
+    /// Fetch from the provider (waiting for a free slot) and store the result.
+    ///
+    /// Entries too old to be served are dropped at the same time, so readings for deleted or
+    /// moved locations don't stay around.
+    async fn fetch(
+        &self,
+        key: CacheKey,
+        coordinates: &Coordinates,
+    ) -> Result<(CurrentWeather, DateTime<Utc>)> {
+        let _permit = self.fetch_limit.acquire().await?;
+        let weather = self.provider.current(coordinates).await?;
+        let cached_at = Utc::now();
+        let max_age = self.fresh_for + self.stale_for;
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (_, entry_cached_at)| cached_at - *entry_cached_at < max_age);
+        entries.insert(key, (weather.clone(), cached_at));
+        Ok((weather, cached_at))
+    }
+}
+
+impl<P> Inner<P> {
+    /// A value from `cache` if it is fresh, otherwise from `fetch` (waiting for a free slot)
+    async fn cached<K: Eq + Hash, V: Clone>(
+        &self,
+        cache: &Expiring<K, V>,
+        key: K,
+        fetch: impl Future<Output = Result<V>>,
+    ) -> Result<V> {
+        if let Some(value) = cache.get(&key, self.fresh_for) {
+            return Ok(value);
+        }
+
+        let value = {
+            let _permit = self.fetch_limit.acquire().await?;
+            fetch.await?
+        };
+        cache.insert(key, value.clone(), self.fresh_for);
+        Ok(value)
+    }
+}
+
+fn cache_key(coordinates: &Coordinates) -> CacheKey {
+    (
+        coordinates.latitude().to_bits(),
+        coordinates.longitude().to_bits(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::{AirQuality, DailyForecast, HourlyForecast};
+    use chrono_tz::Tz;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+    /// Provider counting its calls, reporting the call number as the temperature
+    #[derive(Default)]
+    struct CountingProvider {
+        calls: AtomicU32,
+        forecast_calls: AtomicU32,
+        failing: AtomicBool,
+    }
+
+    impl WeatherProvider for CountingProvider {
+        async fn current(&self, _coordinates: &Coordinates) -> Result<CurrentWeather> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if self.failing.load(Ordering::SeqCst) {
+                anyhow::bail!("upstream unavailable");
+            }
+            Ok(CurrentWeather {
+                temperature_celsius: call as f32,
+                feels_like_celsius: call as f32,
+                humidity: 50,
+                description: "clear sky".to_string(),
+                wind_speed_ms: 1.0,
+                wind_direction_degrees: None,
+                pressure_hpa: None,
+                visibility_m: None,
+                cloud_cover: None,
+                sunrise: None,
+                sunset: None,
+            })
+        }
+
+        async fn hourly(&self, _: &Coordinates, _: usize) -> Result<Vec<HourlyForecast>> {
+            self.forecast_calls.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![])
+        }
+
+        async fn daily(&self, _: &Coordinates, _: Tz, _: usize) -> Result<Vec<DailyForecast>> {
+            Ok(vec![])
+        }
+
+        async fn air_quality(&self, _: &Coordinates) -> Result<AirQuality> {
+            anyhow::bail!("not supported")
+        }
+    }
+
+    fn berlin() -> Coordinates {
+        Coordinates::new(52.52, 13.405).unwrap()
+    }
+
+    #[tokio::test]
+    async fn serves_fresh_readings_from_the_cache() {
+        let cache = WeatherCache::new(
+            CountingProvider::default(),
+            Duration::minutes(10),
+            Duration::hours(1),
+            2,
+        );
+
+        let first = cache.current(&berlin()).await.unwrap();
+        let second = cache.current(&berlin()).await.unwrap();
+
+        assert_eq!(cache.provider().calls.load(Ordering::SeqCst), 1);
+        assert_eq!(first, second);
+        assert!(!second.stale);
+    }
+
+    #[tokio::test]
+    async fn serves_stale_readings_while_revalidating() {
+        let cache = WeatherCache::new(
+            CountingProvider::default(),
+            Duration::zero(),
+            Duration::hours(1),
+            2,
+        );
+
+        cache.current(&berlin()).await.unwrap();
+        let stale = cache.current(&berlin()).await.unwrap();
+        assert!(stale.stale);
+        assert_eq!(stale.weather.temperature_celsius, 1.0);
+
+        // Let the background refresh finish
+        let key = cache_key(&berlin());
+        while cache.inner.refreshing.lock().unwrap().contains(&key) {
+            tokio::task::yield_now().await;
+        }
+        let refreshed = cache.current(&berlin()).await.unwrap();
+        assert_eq!(refreshed.weather.temperature_celsius, 2.0);
+    }
+
+    #[tokio::test]
+    async fn fetches_instead_of_serving_stale_readings_when_asked() {
+        let cache = WeatherCache::new(
+            CountingProvider::default(),
+            Duration::zero(),
+            Duration::hours(1),
+            2,
+        );
+
+        cache.current(&berlin()).await.unwrap();
+        let fresh = cache.fresh(&berlin()).await.unwrap();
+        assert!(!fresh.stale);
+        assert_eq!(fresh.weather.temperature_celsius, 2.0);
+
+        let refreshed = cache.refresh(&berlin()).await.unwrap();
+        assert_eq!(refreshed.weather.temperature_celsius, 3.0);
+        assert_eq!(cache.provider().calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn evicts_readings_too_old_to_serve() {
+        let cache = WeatherCache::new(
+            CountingProvider::default(),
+            Duration::zero(),
+            Duration::zero(),
+            2,
+        );
+
+        cache.current(&berlin()).await.unwrap();
+        let elsewhere = Coordinates::new(35.68, 139.69).unwrap();
+        cache.current(&elsewhere).await.unwrap();
+
+        let entries = cache.inner.entries.lock().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries.contains_key(&cache_key(&elsewhere)));
+    }
+
+    #[tokio::test]
+    async fn caches_forecasts_for_the_freshness_window() {
+        let cache = WeatherCache::new(
+            CountingProvider::default(),
+            Duration::minutes(10),
+            Duration::hours(1),
+            2,
+        );
+
+        cache.hourly(&berlin(), 24).await.unwrap();
+        cache.hourly(&berlin(), 24).await.unwrap();
+        assert_eq!(cache.provider().forecast_calls.load(Ordering::SeqCst), 1);
+
+        // A different length is a different forecast
+        cache.hourly(&berlin(), 48).await.unwrap();
+        assert_eq!(cache.provider().forecast_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_old_readings_when_the_provider_fails() {
+        let cache = WeatherCache::new(
+            CountingProvider::default(),
+            Duration::zero(),
+            Duration::zero(),
+            2,
+        );
+
+        cache.current(&berlin()).await.unwrap();
+        cache.provider().failing.store(true, Ordering::SeqCst);
+
+        let fallback = cache.current(&berlin()).await.unwrap();
+        assert!(fallback.stale);
+        assert_eq!(fallback.weather.temperature_celsius, 1.0);
+
+        let elsewhere = Coordinates::new(35.68, 139.69).unwrap();
+        assert!(cache.current(&elsewhere).await.is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::de::DeserializeOwned;
+use std::future::Future;
+
+use crate::config::{ServerConfig, WeatherProviderKind};
+use crate::location::Coordinates;
+
+pub mod air;
+mod cache;
+pub mod notify;
+mod openmeteo;
+mod openweather;
+pub mod rules;
+pub mod units;
+
+pub use cache::{CachedWeather, WeatherCache};
+pub use openmeteo::OpenMeteo;
+pub use openweather::OpenWeather;
+
+/// Current conditions at a point, independent of the provider
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrentWeather {
+    pub temperature_celsius: f32,
+    pub feels_like_celsius: f32,
+    pub humidity: u8,
+    pub description: String,
+    pub wind_speed_ms: f32,
+    /// Direction the wind blows from, in degrees clockwise from north
+    pub wind_direction_degrees: Option<u16>,
+    /// Sea-level pressure
+    pub pressure_hpa: Option<f32>,
+    pub visibility_m: Option<f32>,
+    /// Cloud cover, 0-100%
+    pub cloud_cover: Option<u8>,
+    pub sunrise: Option<DateTime<Utc>>,
+    pub sunset: Option<DateTime<Utc>>,
+}
+
+/// Current air quality at a point; pollutant concentrations are in µg/m³
+#[derive(Debug, Clone, PartialEq)]
+pub struct AirQuality {
+    pub pm2_5: f32,
+    pub pm10: f32,
+    pub ozone: f32,
+    pub nitrogen_dioxide: f32,
+    /// `None` if the provider does not report UV
+    pub uv_index: Option<f32>,
+}
+
+/// Forecast for one step of an hourly forecast
+#[derive(Debug, Clone, PartialEq)]
+pub struct HourlyForecast {
+    pub time: DateTime<Utc>,
+    pub temperature_celsius: f32,
+    /// Chance of precipitation, 0-100%
+    pub precipitation_probability: u8,
+    pub description: String,
+}
+
+/// Forecast for one local calendar day
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyForecast {
+    pub date: NaiveDate,
+    pub temperature_min_celsius: f32,
+    pub temperature_max_celsius: f32,
+    /// Highest chance of precipitation during the day, 0-100%
+    pub precipitation_probability: u8,
+    pub description: String,
+}
+
+/// A source of weather data
+pub trait WeatherProvider {
+    /// Current conditions at the given coordinates
+    fn current(
+        &self,
+        coordinates: &Coordinates,
+    ) -> impl Future<Output = Result<CurrentWeather>> + Send;
+
+    /// Forecast for (up to) the next `hours` hours, earliest first
+    fn hourly(
+        &self,
+        coordinates: &Coordinates,
+        hours: usize,
+    ) -> impl Future<Output = Result<Vec<HourlyForecast>>> + Send;
+
+    /// Forecast for (up to) `days` days starting today, with days in `timezone`
+    fn daily(
+        &self,
+        coordinates: &Coordinates,
+        timezone: Tz,
+        days: usize,
+    ) -> impl Future<Output = Result<Vec<DailyForecast>>> + Send;
+
+    /// Current air quality (and UV index, if available) at the given coordinates
+    fn air_quality(
+        &self,
+        coordinates: &Coordinates,
+    ) -> impl Future<Output = Result<AirQuality>> + Send;
+}
+
+/// The weather provider selected in the server config
+#[derive(Debug, Clone)]
+pub enum WeatherClient {
+    OpenWeather(OpenWeather),
+    OpenMeteo(OpenMeteo),
+}
+
+impl WeatherClient {
+    /// Build the configured provider around a shared HTTP client
+    pub fn from_config(config: &ServerConfig, http: reqwest::Client) -> Result<Self> {
+        match (config.weather_provider, &config.weather_api_key) {
+            (WeatherProviderKind::OpenMeteo, _) | (WeatherProviderKind::Auto, None) => {
+                Ok(WeatherClient::OpenMeteo(OpenMeteo::new(
+                    http,
+                    &config.openmeteo_base_url,
+                    &config.openmeteo_air_quality_base_url,
+                )))
+            }
+            (WeatherProviderKind::OpenWeather | WeatherProviderKind::Auto, Some(api_key)) => {
+                Ok(WeatherClient::OpenWeather(OpenWeather::new(
+                    http,
+                    &config.openweather_base_url,
+                    api_key,
+                )))
+            }
+            (WeatherProviderKind::OpenWeather, None) => {
+                anyhow::bail!("The OpenWeatherMap weather provider needs OPENWEATHER_API_KEY")
+            }
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WeatherClient::OpenWeather(_) => "openweather",
+            WeatherClient::OpenMeteo(_) => "openmeteo",
+        }
+    }
+}
+
+impl WeatherProvider for WeatherClient {
+    async fn current(&self, coordinates: &Coordinates) -> Result<CurrentWeather> {
+        match self {
+            WeatherClient::OpenWeather(provider) => provider.current(coordinates).await,
+            WeatherClient::OpenMeteo(provider) => provider.current(coordinates).await,
+        }
+    }
+
+    async fn hourly(&self, coordinates: &Coordinates, hours: usize) -> Result<Vec<HourlyForecast>> {
+        match self {
+            WeatherClient::OpenWeather(provider) => provider.hourly(coordinates, hours).await,
+            WeatherClient::OpenMeteo(provider) => provider.hourly(coordinates, hours).await,
+        }
+    }
+
+    async fn daily(
+        &self,
+        coordinates: &Coordinates,
+        timezone: Tz,
+        days: usize,
+    ) -> Result<Vec<DailyForecast>> {
+        match self {
+            WeatherClient::OpenWeather(provider) => {
+                provider.daily(coordinates, timezone, days).await
+            }
+            WeatherClient::OpenMeteo(provider) => provider.daily(coordinates, timezone, days).await,
+        }
+    }
+
+    async fn air_quality(&self, coordinates: &Coordinates) -> Result<AirQuality> {
+        match self {
+            WeatherClient::OpenWeather(provider) => provider.air_quality(coordinates).await,
+            WeatherClient::OpenMeteo(provider) => provider.air_quality(coordinates).await,
+        }
+    }
+}
+
+/// GET a JSON document from a provider, turning HTTP errors into readable ones.
+///
+/// The URL is left out of errors since it may carry an API key.
+async fn get_json<T: DeserializeOwned>(http: &reqwest::Client, url: &str) -> Result<T> {
+    let response = http
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| e.without_url())
+        .context("Failed to fetch weather data")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("Weather API error: {} - {}", status, body);
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| e.without_url())
+        .context("Failed to parse weather data")
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use axum::{routing::get, Router};
+
+    /// Serve a fixed JSON body on `path` from a local server, returning its base URL
+    pub async fn serve(path: &'static str, body: &'static str) -> String {
+        let app = Router::new().route(
+            path,
+            get(move || async move { ([("content-type", "application/json")], body) }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        format!("http://{}", address)
+    }
+}
@@ -0,0 +1,92 @@
+use anyhow::{Context, Result};
+use std::future::Future;
+
+use crate::config::ServerConfig;
+use crate::models::weather_alert::{AlertStatus, WeatherAlert};
+
+/// Somewhere weather alerts are delivered when they fire or resolve
+pub trait NotificationHook {
+    fn notify(&self, alert: &WeatherAlert) -> impl Future<Output = Result<()>> + Send;
+}
+
+/// Write alerts to the server log
+#[derive(Debug, Clone, Copy)]
+pub struct LogHook;
+
+impl NotificationHook for LogHook {
+    async fn notify(&self, alert: &WeatherAlert) -> Result<()> {
+        match alert.status {
+            AlertStatus::Firing => tracing::warn!(
+                "Weather alert firing for {}: {}",
+                alert.location_name,
+                alert.message
+            ),
+            AlertStatus::Resolved => tracing::info!(
+                "Weather alert resolved for {}: {}",
+                alert.location_name,
+                alert.message
+            ),
+        }
+        Ok(())
+    }
+}
+
+/// POST alerts as JSON to a URL
+#[derive(Debug, Clone)]
+pub struct WebhookHook {
+    http: reqwest::Client,
+    url: String,
+}
+
+impl WebhookHook {
+    pub fn new(http: reqwest::Client, url: &str) -> Self {
+        Self {
+            http,
+            url: url.to_string(),
+        }
+    }
+}
+
+impl NotificationHook for WebhookHook {
+    async fn notify(&self, alert: &WeatherAlert) -> Result<()> {
+        let response = self
+            .http
+            .post(&self.url)
+            .json(alert)
+            .send()
+            .await
+            .context("Failed to call alert webhook")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Alert webhook returned {}", response.status());
+        }
+        Ok(())
+    }
+}
+
+/// The notification hooks selected in the server config; alerts are always logged
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    webhook: Option<WebhookHook>,
+}
+
+impl Notifier {
+    pub fn from_config(config: &ServerConfig, http: reqwest::Client) -> Self {
+        Self {
+            webhook: config
+                .weather_alert_webhook_url
+                .as_deref()
+                .map(|url| WebhookHook::new(http, url)),
+        }
+    }
+}
+
+impl NotificationHook for Notifier {
+    async fn notify(&self, alert: &WeatherAlert) -> Result<()> {
+        LogHook.notify(alert).await?;
+        if let Some(webhook) = &self.webhook {
+            webhook.notify(alert).await?;
+        }
+        Ok(())
+    }
+}
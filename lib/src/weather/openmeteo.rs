@@ -0,0 +1,401 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+
+use super::{get_json, AirQuality, CurrentWeather, DailyForecast, HourlyForecast, WeatherProvider};
+use crate::location::Coordinates;
+
+/// Open-Meteo forecast and air quality APIs (no API key needed)
+#[derive(Debug, Clone)]
+pub struct OpenMeteo {
+    http: reqwest::Client,
+    base_url: String,
+    air_quality_base_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoResponse {
+    current: OpenMeteoCurrent,
+    /// Today's sun times (requested with `forecast_days=1`)
+    #[serde(default)]
+    daily: Option<OpenMeteoSunTimes>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoCurrent {
+    temperature_2m: f32,
+    apparent_temperature: f32,
+    relative_humidity_2m: u8,
+    weather_code: u8,
+    wind_speed_10m: f32,
+    #[serde(default)]
+    wind_direction_10m: Option<f32>,
+    #[serde(default)]
+    pressure_msl: Option<f32>,
+    /// Metres
+    #[serde(default)]
+    visibility: Option<f32>,
+    #[serde(default)]
+    cloud_cover: Option<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoSunTimes {
+    /// Unix timestamps (requested with `timeformat=unixtime`)
+    sunrise: Vec<i64>,
+    sunset: Vec<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoHourlyResponse {
+    hourly: OpenMeteoHourly,
+}
+
+/// Hourly series, one value per hour in each array
+#[derive(Debug, Deserialize)]
+struct OpenMeteoHourly {
+    /// Unix timestamps (requested with `timeformat=unixtime`)
+    time: Vec<i64>,
+    temperature_2m: Vec<f32>,
+    precipitation_probability: Vec<Option<u8>>,
+    weather_code: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoDailyResponse {
+    daily: OpenMeteoDaily,
+}
+
+/// Daily series, one value per local day in each array
+#[derive(Debug, Deserialize)]
+struct OpenMeteoDaily {
+    time: Vec<NaiveDate>,
+    temperature_2m_min: Vec<f32>,
+    temperature_2m_max: Vec<f32>,
+    precipitation_probability_max: Vec<Option<u8>>,
+    weather_code: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoAirQualityResponse {
+    current: OpenMeteoAirQuality,
+}
+
+/// Concentrations in µg/m³
+#[derive(Debug, Deserialize)]
+struct OpenMeteoAirQuality {
+    pm2_5: f32,
+    pm10: f32,
+    ozone: f32,
+    nitrogen_dioxide: f32,
+    #[serde(default)]
+    uv_index: Option<f32>,
+}
+
+impl OpenMeteo {
+    pub fn new(http: reqwest::Client, base_url: &str, air_quality_base_url: &str) -> Self {
+        Self {
+            http,
+            base_url: base_url.to_string(),
+            air_quality_base_url: air_quality_base_url.to_string(),
+        }
+    }
+}
+
+impl WeatherProvider for OpenMeteo {
+    async fn current(&self, coordinates: &Coordinates) -> Result<CurrentWeather> {
+        let url = format!(
+            "{}/v1/forecast?latitude={}&longitude={}&current=temperature_2m,apparent_temperature,\
+             relative_humidity_2m,weather_code,wind_speed_10m,wind_direction_10m,pressure_msl,\
+             visibility,cloud_cover&wind_speed_unit=ms&daily=sunrise,sunset&forecast_days=1\
+             &timeformat=unixtime&timezone=auto",
+            self.base_url,
+            coordinates.latitude(),
+            coordinates.longitude()
+        );
+
+        let response: OpenMeteoResponse = get_json(&self.http, &url).await?;
+        let current = response.current;
+        let (sunrise, sunset) = response
+            .daily
+            .map(|daily| (first_time(&daily.sunrise), first_time(&daily.sunset)))
+            .unwrap_or_default();
+
+        Ok(CurrentWeather {
+            temperature_celsius: current.temperature_2m,
+            feels_like_celsius: current.apparent_temperature,
+            humidity: current.relative_humidity_2m,
+            description: describe_weather_code(current.weather_code).to_string(),
+            wind_speed_ms: current.wind_speed_10m,
+            wind_direction_degrees: current
+                .wind_direction_10m
+                .map(|degrees| degrees.round() as u16 % 360),
+            pressure_hpa: current.pressure_msl,
+            visibility_m: current.visibility,
+            cloud_cover: current.cloud_cover,
+            sunrise,
+            sunset,
+        })
+    }
+
+    async fn hourly(&self, coordinates: &Coordinates, hours: usize) -> Result<Vec<HourlyForecast>> {
+        let url = format!(
+            "{}/v1/forecast?latitude={}&longitude={}&hourly=temperature_2m,\
+             precipitation_probability,weather_code&forecast_hours={}&timeformat=unixtime",
+            self.base_url,
+            coordinates.latitude(),
+            coordinates.longitude(),
+            hours
+        );
+
+        let response: OpenMeteoHourlyResponse = get_json(&self.http, &url).await?;
+        let hourly = response.hourly;
+
+        let forecast = hourly
+            .time
+            .iter()
+            .zip(&hourly.temperature_2m)
+            .zip(&hourly.precipitation_probability)
+            .zip(&hourly.weather_code)
+            .filter_map(|(((&time, &temperature), &probability), &code)| {
+                Some(HourlyForecast {
+                    time: DateTime::<Utc>::from_timestamp(time, 0)?,
+                    temperature_celsius: temperature,
+                    precipitation_probability: probability.unwrap_or(0),
+                    description: describe_weather_code(code).to_string(),
+                })
+            })
+            .take(hours)
+            .collect();
+
+        Ok(forecast)
+    }
+
+    async fn daily(
+        &self,
+        coordinates: &Coordinates,
+        timezone: Tz,
+        days: usize,
+    ) -> Result<Vec<DailyForecast>> {
+        let url = format!(
+            "{}/v1/forecast?latitude={}&longitude={}&daily=weather_code,temperature_2m_min,\
+             temperature_2m_max,precipitation_probability_max&forecast_days={}&timezone={}",
+            self.base_url,
+            coordinates.latitude(),
+            coordinates.longitude(),
+            days,
+            urlencoding::encode(timezone.name())
+        );
+
+        let response: OpenMeteoDailyResponse = get_json(&self.http, &url).await?;
+        let daily = response.daily;
+
+        let forecast = daily
+            .time
+            .iter()
+            .zip(&daily.temperature_2m_min)
+            .zip(&daily.temperature_2m_max)
+            .zip(&daily.precipitation_probability_max)
+            .zip(&daily.weather_code)
+            .map(
+                |((((&date, &min), &max), &probability), &code)| DailyForecast {
+                    date,
+                    temperature_min_celsius: min,
+                    temperature_max_celsius: max,
+                    precipitation_probability: probability.unwrap_or(0),
+                    description: describe_weather_code(code).to_string(),
+                },
+            )
+            .take(days)
+            .collect();
+
+        Ok(forecast)
+    }
+
+    async fn air_quality(&self, coordinates: &Coordinates) -> Result<AirQuality> {
+        let url = format!(
+            "{}/v1/air-quality?latitude={}&longitude={}\
+             &current=pm2_5,pm10,ozone,nitrogen_dioxide,uv_index",
+            self.air_quality_base_url,
+            coordinates.latitude(),
+            coordinates.longitude()
+        );
+
+        let response: OpenMeteoAirQualityResponse = get_json(&self.http, &url).await?;
+        let current = response.current;
+
+        Ok(AirQuality {
+            pm2_5: current.pm2_5,
+            pm10: current.pm10,
+            ozone: current.ozone,
+            nitrogen_dioxide: current.nitrogen_dioxide,
+            uv_index: current.uv_index,
+        })
+    }
+}
+
+fn first_time(timestamps: &[i64]) -> Option<DateTime<Utc>> {
+    DateTime::<Utc>::from_timestamp(*timestamps.first()?, 0)
+}
+
+/// Describe a WMO weather interpretation code, in the style of OpenWeatherMap descriptions
+fn describe_weather_code(code: u8) -> &'static str {
+    match code {
+        0 => "clear sky",
+        1 => "mainly clear",
+        2 => "partly cloudy",
+        3 => "overcast",
+        45 => "fog",
+        48 => "depositing rime fog",
+        51 => "light drizzle",
+        53 => "moderate drizzle",
+        55 => "dense drizzle",
+        56 => "light freezing drizzle",
+        57 => "dense freezing drizzle",
+        61 => "light rain",
+        63 => "moderate rain",
+        65 => "heavy rain",
+        66 => "light freezing rain",
+        67 => "heavy freezing rain",
+        71 => "light snow",
+        73 => "moderate snow",
+        75 => "heavy snow",
+        77 => "snow grains",
+        80 => "light rain showers",
+        81 => "moderate rain showers",
+        82 => "violent rain showers",
+        85 => "light snow showers",
+        86 => "heavy snow showers",
+        95 => "thunderstorm",
+        96 => "thunderstorm with light hail",
+        99 => "thunderstorm with heavy hail",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::mock;
+
+    const RESPONSE: &str = r#"{
+        "latitude": 52.52,
+        "longitude": 13.419998,
+        "current": {
+            "time": "2026-10-18T15:00",
+            "interval": 900,
+            "temperature_2m": 9.8,
+            "apparent_temperature": 7.1,
+            "relative_humidity_2m": 76,
+            "weather_code": 61,
+            "wind_speed_10m": 3.6,
+            "wind_direction_10m": 247,
+            "pressure_msl": 1008.4,
+            "visibility": 24140.0,
+            "cloud_cover": 100
+        },
+        "daily": {
+            "time": [1792274400],
+            "sunrise": [1792301520],
+            "sunset": [1792339380]
+        }
+    }"#;
+
+    #[tokio::test]
+    async fn parses_current_weather() {
+        let base_url = mock::serve("/v1/forecast", RESPONSE).await;
+        let provider = OpenMeteo::new(reqwest::Client::new(), &base_url, &base_url);
+
+        let coordinates = Coordinates::new(52.52, 13.405).unwrap();
+        let weather = provider.current(&coordinates).await.unwrap();
+
+        assert_eq!(weather.temperature_celsius, 9.8);
+        assert_eq!(weather.feels_like_celsius, 7.1);
+        assert_eq!(weather.humidity, 76);
+        assert_eq!(weather.description, "light rain");
+        assert_eq!(weather.wind_direction_degrees, Some(247));
+        assert_eq!(weather.pressure_hpa, Some(1008.4));
+        assert_eq!(weather.visibility_m, Some(24140.0));
+        assert_eq!(weather.cloud_cover, Some(100));
+        assert_eq!(weather.sunrise.map(|t| t.timestamp()), Some(1792301520));
+    }
+
+    #[tokio::test]
+    async fn parses_hourly_forecast() {
+        let body = r#"{
+            "hourly": {
+                "time": [1792335600, 1792339200, 1792342800],
+                "temperature_2m": [9.8, 9.1, 8.4],
+                "precipitation_probability": [40, null, 75],
+                "weather_code": [3, 61, 63]
+            }
+        }"#;
+        let base_url = mock::serve("/v1/forecast", body).await;
+        let provider = OpenMeteo::new(reqwest::Client::new(), &base_url, &base_url);
+
+        let coordinates = Coordinates::new(52.52, 13.405).unwrap();
+        let forecast = provider.hourly(&coordinates, 2).await.unwrap();
+
+        assert_eq!(forecast.len(), 2);
+        assert_eq!(forecast[0].time.timestamp(), 1792335600);
+        assert_eq!(forecast[0].description, "overcast");
+        assert_eq!(forecast[1].precipitation_probability, 0);
+        assert_eq!(forecast[1].temperature_celsius, 9.1);
+    }
+
+    #[tokio::test]
+    async fn parses_daily_forecast() {
+        let body = r#"{
+            "daily": {
+                "time": ["2026-10-18", "2026-10-19"],
+                "temperature_2m_min": [6.2, 4.9],
+                "temperature_2m_max": [12.4, 11.0],
+                "precipitation_probability_max": [80, 15],
+                "weather_code": [63, 2]
+            }
+        }"#;
+        let base_url = mock::serve("/v1/forecast", body).await;
+        let provider = OpenMeteo::new(reqwest::Client::new(), &base_url, &base_url);
+
+        let coordinates = Coordinates::new(52.52, 13.405).unwrap();
+        let forecast = provider
+            .daily(&coordinates, chrono_tz::Europe::Berlin, 7)
+            .await
+            .unwrap();
+
+        assert_eq!(forecast.len(), 2);
+        assert_eq!(
+            forecast[0].date,
+            NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()
+        );
+        assert_eq!(forecast[0].temperature_min_celsius, 6.2);
+        assert_eq!(forecast[0].temperature_max_celsius, 12.4);
+        assert_eq!(forecast[0].precipitation_probability, 80);
+        assert_eq!(forecast[1].description, "partly cloudy");
+    }
+
+    #[tokio::test]
+    async fn parses_air_quality() {
+        let body = r#"{
+            "current": {
+                "time": 1792335600,
+                "interval": 3600,
+                "pm2_5": 38.4,
+                "pm10": 52.1,
+                "ozone": 61.0,
+                "nitrogen_dioxide": 17.3,
+                "uv_index": 2.35
+            }
+        }"#;
+        let base_url = mock::serve("/v1/air-quality", body).await;
+        let provider = OpenMeteo::new(reqwest::Client::new(), &base_url, &base_url);
+
+        let coordinates = Coordinates::new(52.52, 13.405).unwrap();
+        let air = provider.air_quality(&coordinates).await.unwrap();
+
+        assert_eq!(air.pm2_5, 38.4);
+        assert_eq!(air.nitrogen_dioxide, 17.3);
+        assert_eq!(air.uv_index, Some(2.35));
+    }
+}
@@ -0,0 +1,350 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Timelike, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+
+use super::{get_json, AirQuality, CurrentWeather, DailyForecast, HourlyForecast, WeatherProvider};
+use crate::location::Coordinates;
+use crate::models::weather::{OpenWeatherCondition, OpenWeatherResponse};
+
+/// Steps in the 5 day / 3 hour forecast
+const FORECAST_STEPS: usize = 40;
+/// Hours between forecast steps
+const FORECAST_STEP_HOURS: usize = 3;
+
+/// OpenWeatherMap current weather, 5 day / 3 hour forecast and air pollution APIs.
+///
+/// The free forecast only has 3-hourly steps, so hourly forecasts come in 3 hour steps
+/// and daily forecasts cover at most 5 days. The free APIs have no UV index.
+#[derive(Debug, Clone)]
+pub struct OpenWeather {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherForecastResponse {
+    list: Vec<OpenWeatherForecastStep>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherForecastStep {
+    /// Unix timestamp
+    dt: i64,
+    main: OpenWeatherForecastMain,
+    weather: Vec<OpenWeatherCondition>,
+    /// Probability of precipitation, 0-1
+    #[serde(default)]
+    pop: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherForecastMain {
+    temp: f32,
+    temp_min: f32,
+    temp_max: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherAirPollutionResponse {
+    list: Vec<OpenWeatherAirPollution>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherAirPollution {
+    components: OpenWeatherAirComponents,
+}
+
+/// Concentrations in µg/m³
+#[derive(Debug, Deserialize)]
+struct OpenWeatherAirComponents {
+    pm2_5: f32,
+    pm10: f32,
+    o3: f32,
+    no2: f32,
+}
+
+impl OpenWeatherForecastStep {
+    fn hourly(&self) -> Option<HourlyForecast> {
+        Some(HourlyForecast {
+            time: DateTime::<Utc>::from_timestamp(self.dt, 0)?,
+            temperature_celsius: self.main.temp,
+            precipitation_probability: (self.pop.clamp(0.0, 1.0) * 100.0).round() as u8,
+            description: describe(&self.weather),
+        })
+    }
+}
+
+impl OpenWeather {
+    pub fn new(http: reqwest::Client, base_url: &str, api_key: &str) -> Self {
+        Self {
+            http,
+            base_url: base_url.to_string(),
+            api_key: api_key.to_string(),
+        }
+    }
+
+    async fn forecast(
+        &self,
+        coordinates: &Coordinates,
+        steps: usize,
+    ) -> Result<Vec<OpenWeatherForecastStep>> {
+        let url = format!(
+            "{}/data/2.5/forecast?lat={}&lon={}&cnt={}&appid={}&units=metric",
+            self.base_url,
+            coordinates.latitude(),
+            coordinates.longitude(),
+            steps.clamp(1, FORECAST_STEPS),
+            self.api_key
+        );
+
+        let response: OpenWeatherForecastResponse = get_json(&self.http, &url).await?;
+        Ok(response.list)
+    }
+}
+
+impl WeatherProvider for OpenWeather {
+    async fn current(&self, coordinates: &Coordinates) -> Result<CurrentWeather> {
+        let url = format!(
+            "{}/data/2.5/weather?lat={}&lon={}&appid={}&units=metric",
+            self.base_url,
+            coordinates.latitude(),
+            coordinates.longitude(),
+            self.api_key
+        );
+
+        let weather_data: OpenWeatherResponse = get_json(&self.http, &url).await?;
+
+        let description = describe(&weather_data.weather);
+        let sys = weather_data.sys.as_ref();
+
+        Ok(CurrentWeather {
+            temperature_celsius: weather_data.main.temp,
+            feels_like_celsius: weather_data.main.feels_like,
+            humidity: weather_data.main.humidity,
+            description,
+            wind_speed_ms: weather_data.wind.speed,
+            wind_direction_degrees: weather_data.wind.deg,
+            pressure_hpa: weather_data.main.pressure,
+            visibility_m: weather_data.visibility,
+            cloud_cover: weather_data.clouds.map(|clouds| clouds.all),
+            sunrise: sys.and_then(|sys| sys.sunrise).and_then(from_timestamp),
+            sunset: sys.and_then(|sys| sys.sunset).and_then(from_timestamp),
+        })
+    }
+
+    async fn hourly(&self, coordinates: &Coordinates, hours: usize) -> Result<Vec<HourlyForecast>> {
+        let steps = self
+            .forecast(coordinates, hours.div_ceil(FORECAST_STEP_HOURS))
+            .await?;
+        Ok(steps
+            .iter()
+            .filter_map(OpenWeatherForecastStep::hourly)
+            .collect())
+    }
+
+    async fn daily(
+        &self,
+        coordinates: &Coordinates,
+        timezone: Tz,
+        days: usize,
+    ) -> Result<Vec<DailyForecast>> {
+        let steps = self.forecast(coordinates, FORECAST_STEPS).await?;
+        let mut forecast = group_by_day(&steps, timezone);
+        forecast.truncate(days);
+        Ok(forecast)
+    }
+
+    async fn air_quality(&self, coordinates: &Coordinates) -> Result<AirQuality> {
+        let url = format!(
+            "{}/data/2.5/air_pollution?lat={}&lon={}&appid={}",
+            self.base_url,
+            coordinates.latitude(),
+            coordinates.longitude(),
+            self.api_key
+        );
+
+        let response: OpenWeatherAirPollutionResponse = get_json(&self.http, &url).await?;
+        let components = response
+            .list
+            .into_iter()
+            .next()
+            .context("No air pollution data for this location")?
+            .components;
+
+        Ok(AirQuality {
+            pm2_5: components.pm2_5,
+            pm10: components.pm10,
+            ozone: components.o3,
+            nitrogen_dioxide: components.no2,
+            uv_index: None,
+        })
+    }
+}
+
+fn from_timestamp(timestamp: i64) -> Option<DateTime<Utc>> {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+}
+
+fn describe(conditions: &[OpenWeatherCondition]) -> String {
+    conditions
+        .first()
+        .map(|w| w.description.clone())
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+/// Combine 3-hourly steps into local days, describing each day by the step nearest noon
+fn group_by_day(steps: &[OpenWeatherForecastStep], timezone: Tz) -> Vec<DailyForecast> {
+    let mut days: Vec<(DailyForecast, u32)> = Vec::new();
+
+    for step in steps {
+        let Some(time) = DateTime::<Utc>::from_timestamp(step.dt, 0) else {
+            continue;
+        };
+        let local = time.with_timezone(&timezone);
+        let date = local.date_naive();
+        let distance_from_noon = local.hour().abs_diff(12);
+        let probability = (step.pop.clamp(0.0, 1.0) * 100.0).round() as u8;
+
+        match days.last_mut() {
+            Some((day, noon_distance)) if day.date == date => {
+                day.temperature_min_celsius = day.temperature_min_celsius.min(step.main.temp_min);
+                day.temperature_max_celsius = day.temperature_max_celsius.max(step.main.temp_max);
+                day.precipitation_probability = day.precipitation_probability.max(probability);
+                if distance_from_noon < *noon_distance {
+                    day.description = describe(&step.weather);
+                    *noon_distance = distance_from_noon;
+                }
+            }
+            _ => days.push((
+                DailyForecast {
+                    date,
+                    temperature_min_celsius: step.main.temp_min,
+                    temperature_max_celsius: step.main.temp_max,
+                    precipitation_probability: probability,
+                    description: describe(&step.weather),
+                },
+                distance_from_noon,
+            )),
+        }
+    }
+
+    days.into_iter().map(|(day, _)| day).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::mock;
+    use chrono::NaiveDate;
+
+    const RESPONSE: &str = r#"{
+        "weather": [{"id": 500, "main": "Rain", "description": "light rain"}],
+        "main": {"temp": 12.5, "feels_like": 11.2, "humidity": 81, "pressure": 1012},
+        "wind": {"speed": 4.1, "deg": 240},
+        "visibility": 8000,
+        "clouds": {"all": 75},
+        "sys": {"country": "DE", "sunrise": 1792300800, "sunset": 1792338600}
+    }"#;
+
+    #[tokio::test]
+    async fn parses_current_weather() {
+        let base_url = mock::serve("/data/2.5/weather", RESPONSE).await;
+        let provider = OpenWeather::new(reqwest::Client::new(), &base_url, "key");
+
+        let coordinates = Coordinates::new(52.52, 13.405).unwrap();
+        let weather = provider.current(&coordinates).await.unwrap();
+
+        assert_eq!(weather.temperature_celsius, 12.5);
+        assert_eq!(weather.humidity, 81);
+        assert_eq!(weather.description, "light rain");
+        assert_eq!(weather.wind_speed_ms, 4.1);
+        assert_eq!(weather.wind_direction_degrees, Some(240));
+        assert_eq!(weather.pressure_hpa, Some(1012.0));
+        assert_eq!(weather.visibility_m, Some(8000.0));
+        assert_eq!(weather.cloud_cover, Some(75));
+        assert_eq!(weather.sunset.map(|t| t.timestamp()), Some(1792338600));
+    }
+
+    #[tokio::test]
+    async fn parses_air_pollution() {
+        let body = r#"{
+            "coord": {"lon": 13.405, "lat": 52.52},
+            "list": [{
+                "main": {"aqi": 2},
+                "components": {
+                    "co": 230.3, "no": 0.1, "no2": 12.6, "o3": 68.7,
+                    "so2": 1.2, "pm2_5": 6.4, "pm10": 9.8, "nh3": 0.5
+                },
+                "dt": 1792335600
+            }]
+        }"#;
+        let base_url = mock::serve("/data/2.5/air_pollution", body).await;
+        let provider = OpenWeather::new(reqwest::Client::new(), &base_url, "key");
+
+        let coordinates = Coordinates::new(52.52, 13.405).unwrap();
+        let air = provider.air_quality(&coordinates).await.unwrap();
+
+        assert_eq!(air.pm2_5, 6.4);
+        assert_eq!(air.ozone, 68.7);
+        assert_eq!(air.nitrogen_dioxide, 12.6);
+        assert_eq!(air.uv_index, None);
+    }
+
+    #[tokio::test]
+    async fn errors_leave_out_the_api_key() {
+        let coordinates = Coordinates::new(52.52, 13.405).unwrap();
+
+        // Unparseable response
+        let base_url = mock::serve("/data/2.5/weather", "{}").await;
+        let provider = OpenWeather::new(reqwest::Client::new(), &base_url, "secret-key");
+        let error = provider.current(&coordinates).await.unwrap_err();
+        assert!(!format!("{:?}", error).contains("secret-key"));
+
+        // Nothing listening
+        let provider = OpenWeather::new(reqwest::Client::new(), "http://127.0.0.1:9", "secret-key");
+        let error = provider.current(&coordinates).await.unwrap_err();
+        assert!(!format!("{:?}", error).contains("secret-key"));
+    }
+
+    fn step(time: &str, temp: f32, pop: f32, description: &str) -> OpenWeatherForecastStep {
+        OpenWeatherForecastStep {
+            dt: time.parse::<DateTime<Utc>>().unwrap().timestamp(),
+            main: OpenWeatherForecastMain {
+                temp,
+                temp_min: temp - 0.5,
+                temp_max: temp + 0.5,
+            },
+            weather: vec![OpenWeatherCondition {
+                description: description.to_string(),
+            }],
+            pop,
+        }
+    }
+
+    #[test]
+    fn groups_steps_into_local_days() {
+        let steps = [
+            step("2026-10-18T18:00:00Z", 10.0, 0.2, "few clouds"),
+            step("2026-10-18T21:00:00Z", 8.0, 0.0, "clear sky"),
+            // Midnight and later in Berlin (UTC+2) belong to the next day
+            step("2026-10-18T22:00:00Z", 7.0, 0.1, "clear sky"),
+            step("2026-10-19T10:00:00Z", 13.0, 0.65, "light rain"),
+            step("2026-10-19T13:00:00Z", 14.0, 0.4, "overcast clouds"),
+        ];
+
+        let days = group_by_day(&steps, chrono_tz::Europe::Berlin);
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, NaiveDate::from_ymd_opt(2026, 10, 18).unwrap());
+        assert_eq!(days[0].temperature_min_celsius, 7.5);
+        assert_eq!(days[0].temperature_max_celsius, 10.5);
+        assert_eq!(days[0].precipitation_probability, 20);
+        assert_eq!(days[1].temperature_min_celsius, 6.5);
+        assert_eq!(days[1].temperature_max_celsius, 14.5);
+        assert_eq!(days[1].precipitation_probability, 65);
+        // 12:00 local is nearer noon than 15:00
+        assert_eq!(days[1].description, "light rain");
+    }
+}
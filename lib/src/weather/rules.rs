@@ -0,0 +1,156 @@
+use chrono::{DateTime, Duration, Utc};
+
+use super::{CurrentWeather, HourlyForecast};
+use crate::models::weather_alert::WeatherRuleKind;
+
+/// Chance of precipitation (%) from which rain counts as expected
+pub const RAIN_PROBABILITY_THRESHOLD: u8 = 50;
+/// Longest look-ahead a rain rule may use, in hours
+pub const MAX_RAIN_WINDOW_HOURS: f32 = 48.0;
+
+/// Result of checking one rule against the latest weather
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleOutcome {
+    pub firing: bool,
+    /// The value compared with the threshold (% chance for rain rules)
+    pub value: f32,
+    pub message: String,
+}
+
+/// Check a rule against current conditions and the hourly forecast
+pub fn evaluate(
+    kind: WeatherRuleKind,
+    threshold: f32,
+    current: &CurrentWeather,
+    hourly: &[HourlyForecast],
+    now: DateTime<Utc>,
+) -> RuleOutcome {
+    match kind {
+        WeatherRuleKind::TemperatureBelow => {
+            let value = current.temperature_celsius;
+            RuleOutcome {
+                firing: value < threshold,
+                value,
+                message: format!("Temperature {:.1}C (alert below {:.1}C)", value, threshold),
+            }
+        }
+        WeatherRuleKind::WindAbove => {
+            let value = current.wind_speed_ms;
+            RuleOutcome {
+                firing: value > threshold,
+                value,
+                message: format!("Wind {:.1} m/s (alert above {:.1} m/s)", value, threshold),
+            }
+        }
+        WeatherRuleKind::RainWithin => {
+            let window_end = now + Duration::minutes((threshold * 60.0) as i64);
+            // Include the step already in progress
+            let value = hourly
+                .iter()
+                .filter(|step| step.time + Duration::hours(1) > now && step.time < window_end)
+                .map(|step| step.precipitation_probability)
+                .max()
+                .unwrap_or(0);
+
+            RuleOutcome {
+                firing: value >= RAIN_PROBABILITY_THRESHOLD,
+                value: value as f32,
+                message: format!("{}% chance of rain within {}h", value, threshold),
+            }
+        }
+    }
+}
+
+/// Check a threshold is usable for a rule kind
+pub fn validate_threshold(kind: WeatherRuleKind, threshold: f32) -> anyhow::Result<()> {
+    if !threshold.is_finite() {
+        anyhow::bail!("Rule threshold must be a number");
+    }
+
+    match kind {
+        WeatherRuleKind::WindAbove if threshold < 0.0 => {
+            anyhow::bail!("Wind speed threshold cannot be negative")
+        }
+        WeatherRuleKind::RainWithin if !(threshold > 0.0 && threshold <= MAX_RAIN_WINDOW_HOURS) => {
+            anyhow::bail!(
+                "Rain look-ahead must be between 0 and {} hours",
+                MAX_RAIN_WINDOW_HOURS
+            )
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn current(temperature: f32, wind: f32) -> CurrentWeather {
+        CurrentWeather {
+            temperature_celsius: temperature,
+            feels_like_celsius: temperature,
+            humidity: 60,
+            description: "clear sky".to_string(),
+            wind_speed_ms: wind,
+            wind_direction_degrees: None,
+            pressure_hpa: None,
+            visibility_m: None,
+            cloud_cover: None,
+            sunrise: None,
+            sunset: None,
+        }
+    }
+
+    fn hour(now: DateTime<Utc>, offset: i64, probability: u8) -> HourlyForecast {
+        HourlyForecast {
+            time: now + Duration::hours(offset),
+            temperature_celsius: 10.0,
+            precipitation_probability: probability,
+            description: "light rain".to_string(),
+        }
+    }
+
+    #[test]
+    fn temperature_and_wind_thresholds() {
+        let now = Utc::now();
+        let weather = current(-1.5, 12.0);
+
+        let frost = evaluate(WeatherRuleKind::TemperatureBelow, 0.0, &weather, &[], now);
+        assert!(frost.firing);
+        assert_eq!(frost.value, -1.5);
+        assert!(!evaluate(WeatherRuleKind::TemperatureBelow, -2.0, &weather, &[], now).firing);
+
+        assert!(!evaluate(WeatherRuleKind::WindAbove, 15.0, &weather, &[], now).firing);
+        assert!(evaluate(WeatherRuleKind::WindAbove, 10.0, &weather, &[], now).firing);
+    }
+
+    #[test]
+    fn rain_only_counts_inside_the_window() {
+        let now = "2026-10-18T12:20:00Z".parse::<DateTime<Utc>>().unwrap();
+        let start = "2026-10-18T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let hourly = [
+            hour(start, -1, 90),
+            hour(start, 0, 20),
+            hour(start, 2, 40),
+            hour(start, 4, 85),
+        ];
+        let weather = current(10.0, 2.0);
+
+        let soon = evaluate(WeatherRuleKind::RainWithin, 3.0, &weather, &hourly, now);
+        assert!(!soon.firing);
+        assert_eq!(soon.value, 40.0);
+
+        let later = evaluate(WeatherRuleKind::RainWithin, 6.0, &weather, &hourly, now);
+        assert!(later.firing);
+        assert_eq!(later.value, 85.0);
+    }
+
+    #[test]
+    fn validates_thresholds() {
+        assert!(validate_threshold(WeatherRuleKind::TemperatureBelow, -10.0).is_ok());
+        assert!(validate_threshold(WeatherRuleKind::WindAbove, -1.0).is_err());
+        assert!(validate_threshold(WeatherRuleKind::RainWithin, 0.0).is_err());
+        assert!(validate_threshold(WeatherRuleKind::RainWithin, 72.0).is_err());
+        assert!(validate_threshold(WeatherRuleKind::RainWithin, f32::NAN).is_err());
+    }
+}
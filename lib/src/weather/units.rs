@@ -0,0 +1,164 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+const MPH_PER_MS: f32 = 2.23694;
+const INHG_PER_HPA: f32 = 0.02953;
+const METRES_PER_MILE: f32 = 1609.344;
+
+/// Unit system weather readings are reported in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitSystem {
+    /// Celsius, m/s, hPa, km
+    #[default]
+    Metric,
+    /// Fahrenheit, mph, inHg, miles
+    Imperial,
+    /// Kelvin, m/s, Pa, metres
+    Si,
+}
+
+impl FromStr for UnitSystem {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "metric" => Ok(UnitSystem::Metric),
+            "imperial" => Ok(UnitSystem::Imperial),
+            "si" => Ok(UnitSystem::Si),
+            _ => anyhow::bail!(
+                "Unknown unit system '{}' (use metric, imperial or si)",
+                value
+            ),
+        }
+    }
+}
+
+impl fmt::Display for UnitSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitSystem::Metric => write!(f, "metric"),
+            UnitSystem::Imperial => write!(f, "imperial"),
+            UnitSystem::Si => write!(f, "si"),
+        }
+    }
+}
+
+/// Short labels for the units of a unit system, e.g. `C` and `m/s`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct UnitLabels {
+    pub temperature: &'static str,
+    pub speed: &'static str,
+    pub pressure: &'static str,
+    pub distance: &'static str,
+}
+
+impl UnitSystem {
+    pub fn temperature(&self, celsius: f32) -> f32 {
+        match self {
+            UnitSystem::Metric => celsius,
+            UnitSystem::Imperial => celsius * 9.0 / 5.0 + 32.0,
+            UnitSystem::Si => celsius + 273.15,
+        }
+    }
+
+    pub fn speed(&self, metres_per_second: f32) -> f32 {
+        match self {
+            UnitSystem::Metric | UnitSystem::Si => metres_per_second,
+            UnitSystem::Imperial => metres_per_second * MPH_PER_MS,
+        }
+    }
+
+    pub fn pressure(&self, hectopascals: f32) -> f32 {
+        match self {
+            UnitSystem::Metric => hectopascals,
+            UnitSystem::Imperial => hectopascals * INHG_PER_HPA,
+            UnitSystem::Si => hectopascals * 100.0,
+        }
+    }
+
+    pub fn distance(&self, metres: f32) -> f32 {
+        match self {
+            UnitSystem::Metric => metres / 1000.0,
+            UnitSystem::Imperial => metres / METRES_PER_MILE,
+            UnitSystem::Si => metres,
+        }
+    }
+
+    pub fn labels(&self) -> UnitLabels {
+        match self {
+            UnitSystem::Metric => UnitLabels {
+                temperature: "C",
+                speed: "m/s",
+                pressure: "hPa",
+                distance: "km",
+            },
+            UnitSystem::Imperial => UnitLabels {
+                temperature: "F",
+                speed: "mph",
+                pressure: "inHg",
+                distance: "mi",
+            },
+            UnitSystem::Si => UnitLabels {
+                temperature: "K",
+                speed: "m/s",
+                pressure: "Pa",
+                distance: "m",
+            },
+        }
+    }
+}
+
+/// The 16-point compass direction for a bearing in degrees, e.g. `SW` for 225
+pub fn compass_direction(degrees: u16) -> &'static str {
+    const POINTS: [&str; 16] = [
+        "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW",
+        "NW", "NNW",
+    ];
+    let index = ((degrees % 360) as f32 / 22.5).round() as usize % POINTS.len();
+    POINTS[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 0.01
+    }
+
+    #[test]
+    fn converts_between_unit_systems() {
+        assert_eq!(UnitSystem::Metric.temperature(21.5), 21.5);
+        assert!(close(UnitSystem::Imperial.temperature(-40.0), -40.0));
+        assert!(close(UnitSystem::Imperial.temperature(100.0), 212.0));
+        assert!(close(UnitSystem::Si.temperature(0.0), 273.15));
+
+        assert!(close(UnitSystem::Imperial.speed(10.0), 22.37));
+        assert!(close(UnitSystem::Imperial.pressure(1013.25), 29.92));
+        assert!(close(UnitSystem::Si.pressure(1013.25), 101325.0));
+        assert!(close(UnitSystem::Metric.distance(10_000.0), 10.0));
+        assert!(close(UnitSystem::Imperial.distance(1609.344), 1.0));
+    }
+
+    #[test]
+    fn parses_unit_systems() {
+        assert_eq!(
+            "Imperial".parse::<UnitSystem>().unwrap(),
+            UnitSystem::Imperial
+        );
+        assert_eq!("si".parse::<UnitSystem>().unwrap(), UnitSystem::Si);
+        assert!("kelvin".parse::<UnitSystem>().is_err());
+    }
+
+    #[test]
+    fn names_compass_directions() {
+        assert_eq!(compass_direction(0), "N");
+        assert_eq!(compass_direction(240), "WSW");
+        assert_eq!(compass_direction(225), "SW");
+        assert_eq!(compass_direction(355), "N");
+        assert_eq!(compass_direction(360), "N");
+    }
+}
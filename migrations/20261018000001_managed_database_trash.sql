@@ -0,0 +1,9 @@
+-- Soft-delete support for managed databases
+
+ALTER TABLE managed_databases ADD COLUMN deleted_at TIMESTAMPTZ;
+ALTER TABLE managed_databases ADD COLUMN trashed_name TEXT;
+
+-- Names only need to be unique among live databases so a trashed name can be reused
+ALTER TABLE managed_databases DROP CONSTRAINT managed_databases_db_name_key;
+CREATE UNIQUE INDEX idx_managed_databases_live_name ON managed_databases(db_name) WHERE deleted_at IS NULL;
+CREATE INDEX idx_managed_databases_deleted_at ON managed_databases(deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,3 @@
+-- Per-database drop protection
+
+ALTER TABLE managed_databases ADD COLUMN protected BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,12 @@
+-- Registry of additional Postgres clusters that can host managed databases
+
+CREATE TABLE database_servers (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    admin_url_encrypted TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- NULL means the database lives on the control cluster
+ALTER TABLE managed_databases
+    ADD COLUMN server TEXT REFERENCES database_servers(name) ON UPDATE CASCADE;
@@ -0,0 +1,7 @@
+-- Default ("current") location per API token
+
+CREATE TABLE default_locations (
+    token_hash TEXT PRIMARY KEY,
+    location_id UUID NOT NULL REFERENCES locations(id) ON DELETE CASCADE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
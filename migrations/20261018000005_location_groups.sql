@@ -0,0 +1,16 @@
+-- Named, ordered groups of locations
+
+CREATE TABLE location_groups (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE location_group_members (
+    group_id UUID NOT NULL REFERENCES location_groups(id) ON DELETE CASCADE,
+    location_id UUID NOT NULL REFERENCES locations(id) ON DELETE CASCADE,
+    position INT NOT NULL,
+    PRIMARY KEY (group_id, location_id)
+);
+
+CREATE INDEX idx_location_group_members_position ON location_group_members(group_id, position);
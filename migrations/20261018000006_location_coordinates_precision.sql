@@ -0,0 +1,19 @@
+-- Store coordinates as DOUBLE PRECISION instead of REAL and enforce valid ranges.
+-- Existing REAL values are rounded to 5 decimals (~1 m) to drop float noise.
+
+ALTER TABLE locations
+    ALTER COLUMN latitude TYPE DOUBLE PRECISION USING round(latitude::numeric, 5)::double precision,
+    ALTER COLUMN longitude TYPE DOUBLE PRECISION USING round(longitude::numeric, 5)::double precision;
+
+-- Rows saved before coordinates were validated may have only one coordinate or values out
+-- of range; clear them so the constraints below can be added.
+UPDATE locations
+SET latitude = NULL, longitude = NULL
+WHERE (latitude IS NULL) <> (longitude IS NULL)
+    OR latitude NOT BETWEEN -90 AND 90
+    OR longitude NOT BETWEEN -180 AND 180;
+
+ALTER TABLE locations
+    ADD CONSTRAINT locations_latitude_range CHECK (latitude BETWEEN -90 AND 90),
+    ADD CONSTRAINT locations_longitude_range CHECK (longitude BETWEEN -180 AND 180),
+    ADD CONSTRAINT locations_coordinates_pair CHECK ((latitude IS NULL) = (longitude IS NULL));
@@ -0,0 +1,18 @@
+-- Team members and where they work from.
+--
+-- A member is either linked to a location (and follows its timezone) or has
+-- a raw timezone. The timezone column always holds the last known zone so a
+-- member keeps it if their location is deleted.
+
+CREATE TABLE team_members (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    email TEXT,
+    location_id UUID REFERENCES locations(id) ON DELETE SET NULL,
+    timezone TEXT NOT NULL,
+    work_start TIME NOT NULL DEFAULT '09:00',
+    work_end TIME NOT NULL DEFAULT '17:00',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_team_members_location ON team_members(location_id);
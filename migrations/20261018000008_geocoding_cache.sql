@@ -0,0 +1,13 @@
+-- Cached upstream geocoding results, keyed by normalised query
+
+CREATE TABLE geocoding_cache (
+    provider TEXT NOT NULL,
+    query TEXT NOT NULL,
+    result_limit INT NOT NULL,
+    results JSONB NOT NULL,
+    cached_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (provider, query, result_limit)
+);
+
+CREATE INDEX idx_geocoding_cache_expires_at ON geocoding_cache(expires_at);
@@ -0,0 +1,17 @@
+-- Periodic snapshots of current weather per location.
+--
+-- Readings are keyed by the time the provider returned them, so recording the
+-- same cached reading twice is a no-op.
+
+CREATE TABLE weather_observations (
+    location_id UUID NOT NULL REFERENCES locations(id) ON DELETE CASCADE,
+    observed_at TIMESTAMPTZ NOT NULL,
+    temperature_celsius REAL NOT NULL,
+    feels_like_celsius REAL NOT NULL,
+    humidity SMALLINT NOT NULL,
+    description TEXT NOT NULL,
+    wind_speed_ms REAL NOT NULL,
+    PRIMARY KEY (location_id, observed_at)
+);
+
+CREATE INDEX idx_weather_observations_observed_at ON weather_observations(observed_at);
@@ -0,0 +1,28 @@
+-- User-defined weather rules per location and the alerts they raise.
+--
+-- An alert is opened when its rule starts firing and resolved when it stops;
+-- a rule has at most one open alert at a time.
+
+CREATE TABLE weather_rules (
+    id UUID PRIMARY KEY,
+    location_id UUID NOT NULL REFERENCES locations(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('temperature_below', 'wind_above', 'rain_within')),
+    -- Degrees Celsius, metres per second or hours, depending on the kind
+    threshold REAL NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_weather_rules_location ON weather_rules(location_id);
+
+CREATE TABLE weather_alerts (
+    id UUID PRIMARY KEY,
+    rule_id UUID NOT NULL REFERENCES weather_rules(id) ON DELETE CASCADE,
+    -- Observed value that made the rule fire (same unit as the threshold, or % for rain)
+    value REAL NOT NULL,
+    message TEXT NOT NULL,
+    fired_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX idx_weather_alerts_open ON weather_alerts(rule_id) WHERE resolved_at IS NULL;
+CREATE INDEX idx_weather_alerts_fired_at ON weather_alerts(fired_at);
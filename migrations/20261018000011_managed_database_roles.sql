@@ -0,0 +1,9 @@
+-- Login role that owns each managed database, handed out by the connection endpoint
+-- instead of the cluster's admin credentials
+
+CREATE TABLE managed_database_roles (
+    database_id UUID PRIMARY KEY REFERENCES managed_databases(id) ON DELETE CASCADE,
+    role_name TEXT NOT NULL,
+    password_encrypted TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
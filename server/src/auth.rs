@@ -6,11 +6,13 @@ use axum::{
 };
 use std::sync::Arc;
 
+use lib::controllers::timer::ApiToken;
+
 use super::state::AppState;
 
 pub async fn auth_middleware(
     State(state): State<Arc<AppState>>,
-    request: Request,
+    mut request: Request,
     next: Next,
 ) -> Result<Response, AuthError> {
     let auth_header = request
@@ -27,6 +29,9 @@ pub async fn auth_middleware(
         return Err(AuthError::InvalidToken);
     }
 
+    let token = ApiToken(token.to_string());
+    request.extensions_mut().insert(token);
+
     Ok(next.run(request).await)
 }
 
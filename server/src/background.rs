@@ -2,7 +2,14 @@ use sqlx::PgPool;
 use std::time::Duration;
 use tokio::time;
 
+use lib::services::database::DatabaseService;
+use lib::services::database_server::DatabaseClusters;
 use lib::services::timer::TimerService;
+use lib::models::weather_alert::AlertStatus;
+use lib::services::weather::WeatherService;
+use lib::services::weather_alert::WeatherAlertService;
+use lib::weather::notify::Notifier;
+use lib::weather::WeatherCache;
 
 /// Background task that checks for expired timers every second
 pub async fn timer_expiration_checker(pool: PgPool) {
@@ -26,3 +33,95 @@ pub async fn timer_expiration_checker(pool: PgPool) {
         }
     }
 }
+
+/// Background task that permanently drops trashed databases once their grace period has elapsed
+pub async fn database_trash_purger(pool: PgPool, clusters: DatabaseClusters, grace_hours: i64) {
+    let mut interval = time::interval(Duration::from_secs(60));
+    let grace_period = chrono::Duration::hours(grace_hours);
+
+    loop {
+        interval.tick().await;
+
+        match DatabaseService::purge_expired_trash(&pool, &clusters, grace_period).await {
+            Ok(purged) => {
+                for database in purged {
+                    tracing::info!(
+                        "Purged trashed database '{}' (id: {})",
+                        database.db_name,
+                        database.id
+                    );
+                }
+            }
+            Err(e) => {
+                tracing::error!("Error purging database trash: {:?}", e);
+            }
+        }
+    }
+}
+
+/// Background task that snapshots current weather for every location and drops old observations
+pub async fn weather_recorder(
+    pool: PgPool,
+    cache: WeatherCache,
+    interval_mins: u64,
+    retention_days: i64,
+) {
+    let mut interval = time::interval(Duration::from_secs(interval_mins * 60));
+
+    loop {
+        interval.tick().await;
+
+        match WeatherService::record_observations(&pool, &cache).await {
+            Ok(recorded) => {
+                tracing::debug!("Recorded {} weather observation(s)", recorded);
+            }
+            Err(e) => {
+                tracing::error!("Error recording weather observations: {:?}", e);
+            }
+        }
+
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(retention_days);
+        match WeatherService::purge_observations(&pool, cutoff).await {
+            Ok(purged) if purged > 0 => {
+                tracing::info!("Purged {} old weather observation(s)", purged);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!("Error purging weather observations: {:?}", e);
+            }
+        }
+    }
+}
+
+/// Background task that evaluates weather rules and delivers alert transitions
+pub async fn weather_alert_evaluator(
+    pool: PgPool,
+    cache: WeatherCache,
+    notifier: Notifier,
+    interval_mins: u64,
+) {
+    let mut interval = time::interval(Duration::from_secs(interval_mins * 60));
+
+    loop {
+        interval.tick().await;
+
+        match WeatherAlertService::evaluate(&pool, &cache, &notifier).await {
+            Ok(changed) => {
+                let firing = changed
+                    .iter()
+                    .filter(|alert| alert.status == AlertStatus::Firing)
+                    .count();
+                if !changed.is_empty() {
+                    tracing::debug!(
+                        "{} weather alert(s) fired, {} resolved",
+                        firing,
+                        changed.len() - firing
+                    );
+                }
+            }
+            Err(e) => {
+                tracing::error!("Error evaluating weather rules: {:?}", e);
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use axum::{
     middleware,
-    routing::{delete, get, post},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use std::sync::Arc;
@@ -11,7 +11,12 @@ mod auth;
 mod background;
 mod state;
 
-use lib::{config::ServerConfig, db};
+use lib::{
+    config::ServerConfig,
+    db,
+    services::database_server::DatabaseClusters,
+    weather::{notify::Notifier, WeatherCache, WeatherClient},
+};
 use state::AppState;
 
 #[tokio::main]
@@ -38,13 +43,43 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("Database migrations completed");
 
     // Create application state
+    let clusters = DatabaseClusters::new(config.clone());
+    let http = reqwest::Client::new();
+    let weather = WeatherClient::from_config(&config, http.clone())?;
+    tracing::info!("Using weather provider {}", weather.name());
+    let weather = WeatherCache::from_config(&config, weather);
+
     let state = Arc::new(AppState {
         db: pool.clone(),
         config: config.clone(),
+        clusters: clusters.clone(),
+        http,
+        weather,
     });
 
     // Start background tasks
     tokio::spawn(background::timer_expiration_checker(pool.clone()));
+    tokio::spawn(background::database_trash_purger(
+        pool.clone(),
+        clusters,
+        config.database_trash_grace_hours,
+    ));
+    if config.weather_alert_interval_mins > 0 {
+        tokio::spawn(background::weather_alert_evaluator(
+            pool.clone(),
+            state.weather.clone(),
+            Notifier::from_config(&config, state.http.clone()),
+            config.weather_alert_interval_mins,
+        ));
+    }
+    if config.weather_snapshot_interval_mins > 0 {
+        tokio::spawn(background::weather_recorder(
+            pool.clone(),
+            state.weather.clone(),
+            config.weather_snapshot_interval_mins,
+            config.weather_retention_days,
+        ));
+    }
     tracing::info!("Background tasks started");
 
     // Build the application with routes
@@ -54,12 +89,18 @@ async fn main() -> anyhow::Result<()> {
         .nest("/api/v1/timers", timer_routes())
         // Location routes (protected)
         .nest("/api/v1/locations", location_routes())
+        // Location group routes (protected)
+        .nest("/api/v1/location-groups", location_group_routes())
+        // Team member routes (protected)
+        .nest("/api/v1/team", team_routes())
         // Weather routes (protected)
         .nest("/api/v1/weather", weather_routes())
         // Geocoding routes (protected)
         .nest("/api/v1/geocoding", geocoding_routes())
         // Database management routes (protected)
         .nest("/api/v1/databases", database_routes())
+        // Database server registry routes (protected)
+        .nest("/api/v1/database-servers", database_server_routes())
         .route_layer(middleware::from_fn_with_state(
             state.clone(),
             auth::auth_middleware,
@@ -100,16 +141,60 @@ fn location_routes() -> Router<Arc<AppState>> {
         .route("/", post(location::create_location))
         .route("/", get(location::list_locations))
         .route("/{id}", get(location::get_location))
+        .route("/{id}", patch(location::update_location))
         .route("/{id}", delete(location::delete_location))
         .route("/{id}/time", get(location::get_location_time))
         .route("/times", get(location::list_location_times))
+        .route("/overlap", get(location::get_location_overlap))
+        .route("/convert", get(location::convert_location_time))
+        .route("/default", get(location::get_default_location))
+        .route("/default", put(location::set_default_location))
+        .route("/default", delete(location::clear_default_location))
+}
+
+fn location_group_routes() -> Router<Arc<AppState>> {
+    use lib::controllers::location_group;
+
+    Router::new()
+        .route("/", post(location_group::create_location_group))
+        .route("/", get(location_group::list_location_groups))
+        .route("/{name}", get(location_group::get_location_group))
+        .route("/{name}", delete(location_group::delete_location_group))
+        .route("/{name}/locations", put(location_group::set_group_locations))
+        .route("/{name}/locations", post(location_group::add_group_location))
+        .route(
+            "/{name}/locations/{location_id}",
+            delete(location_group::remove_group_location),
+        )
+}
+
+fn team_routes() -> Router<Arc<AppState>> {
+    use lib::controllers::team;
+
+    Router::new()
+        .route("/", post(team::create_team_member))
+        .route("/", get(team::list_team_members))
+        .route("/now", get(team::get_team_now))
+        .route("/{id}", get(team::get_team_member))
+        .route("/{id}", patch(team::update_team_member))
+        .route("/{id}", delete(team::delete_team_member))
 }
 
 fn weather_routes() -> Router<Arc<AppState>> {
-    use lib::controllers::weather;
+    use lib::controllers::{weather, weather_alert};
 
     Router::new()
         .route("/locations/{id}", get(weather::get_weather_for_location))
+        .route("/locations/{id}/forecast/hourly", get(weather::get_hourly_forecast))
+        .route("/locations/{id}/forecast/daily", get(weather::get_daily_forecast))
+        .route("/locations/{id}/air-quality", get(weather::get_air_quality))
+        .route("/locations/{id}/history", get(weather::get_weather_history))
+        .route(
+            "/rules",
+            post(weather_alert::create_weather_rule).get(weather_alert::list_weather_rules),
+        )
+        .route("/rules/{id}", delete(weather_alert::delete_weather_rule))
+        .route("/alerts", get(weather_alert::list_weather_alerts))
         .route("/locations", get(weather::get_weather_for_all_locations))
 }
 
@@ -118,6 +203,8 @@ fn geocoding_routes() -> Router<Arc<AppState>> {
 
     Router::new()
         .route("/lookup", get(geocoding::lookup_city))
+        .route("/reverse", get(geocoding::reverse_geocode))
+        .route("/cache", delete(geocoding::purge_geocoding_cache))
 }
 
 fn database_routes() -> Router<Arc<AppState>> {
@@ -129,4 +216,18 @@ fn database_routes() -> Router<Arc<AppState>> {
         .route("/{name}", get(database::get_database))
         .route("/{name}", delete(database::drop_database))
         .route("/{name}/exists", get(database::check_database_exists))
+        .route("/{name}/connection", get(database::get_database_connection))
+        .route("/{name}/undelete", post(database::undelete_database))
+        .route("/{name}/protection", put(database::set_database_protection))
+        .route("/{name}/exec", post(database::exec_sql))
+        .route("/{name}/migrate", post(database::migrate_database))
+}
+
+fn database_server_routes() -> Router<Arc<AppState>> {
+    use lib::controllers::database_server;
+
+    Router::new()
+        .route("/", post(database_server::create_database_server))
+        .route("/", get(database_server::list_database_servers))
+        .route("/{name}", delete(database_server::delete_database_server))
 }